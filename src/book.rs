@@ -0,0 +1,299 @@
+//! Canonical book identification
+
+use data::VERSE_COUNTS;
+use names::{self, Language};
+
+/// Book of the 66-book Protestant canon, in canonical order
+#[derive(Hash, Eq, PartialEq, Ord, PartialOrd, Debug, Clone, Copy)]
+#[allow(missing_docs)]
+pub enum Book {
+    Genesis,
+    Exodus,
+    Leviticus,
+    Numbers,
+    Deuteronomy,
+    Joshua,
+    Judges,
+    Ruth,
+    FirstSamuel,
+    SecondSamuel,
+    FirstKings,
+    SecondKings,
+    FirstChronicles,
+    SecondChronicles,
+    Ezra,
+    Nehemiah,
+    Esther,
+    Job,
+    Psalms,
+    Proverbs,
+    Ecclesiastes,
+    SongOfSongs,
+    Isaiah,
+    Jeremiah,
+    Lamentations,
+    Ezekiel,
+    Daniel,
+    Hosea,
+    Joel,
+    Amos,
+    Obadiah,
+    Jonah,
+    Micah,
+    Nahum,
+    Habakkuk,
+    Zephaniah,
+    Haggai,
+    Zechariah,
+    Malachi,
+    Matthew,
+    Mark,
+    Luke,
+    John,
+    Acts,
+    Romans,
+    FirstCorinthians,
+    SecondCorinthians,
+    Galatians,
+    Ephesians,
+    Philippians,
+    Colossians,
+    FirstThessalonians,
+    SecondThessalonians,
+    FirstTimothy,
+    SecondTimothy,
+    Titus,
+    Philemon,
+    Hebrews,
+    James,
+    FirstPeter,
+    SecondPeter,
+    FirstJohn,
+    SecondJohn,
+    ThirdJohn,
+    Jude,
+    Revelation,
+}
+
+/// All books in canonical order
+pub static BOOKS: [Book; 66] = [
+    Book::Genesis,
+    Book::Exodus,
+    Book::Leviticus,
+    Book::Numbers,
+    Book::Deuteronomy,
+    Book::Joshua,
+    Book::Judges,
+    Book::Ruth,
+    Book::FirstSamuel,
+    Book::SecondSamuel,
+    Book::FirstKings,
+    Book::SecondKings,
+    Book::FirstChronicles,
+    Book::SecondChronicles,
+    Book::Ezra,
+    Book::Nehemiah,
+    Book::Esther,
+    Book::Job,
+    Book::Psalms,
+    Book::Proverbs,
+    Book::Ecclesiastes,
+    Book::SongOfSongs,
+    Book::Isaiah,
+    Book::Jeremiah,
+    Book::Lamentations,
+    Book::Ezekiel,
+    Book::Daniel,
+    Book::Hosea,
+    Book::Joel,
+    Book::Amos,
+    Book::Obadiah,
+    Book::Jonah,
+    Book::Micah,
+    Book::Nahum,
+    Book::Habakkuk,
+    Book::Zephaniah,
+    Book::Haggai,
+    Book::Zechariah,
+    Book::Malachi,
+    Book::Matthew,
+    Book::Mark,
+    Book::Luke,
+    Book::John,
+    Book::Acts,
+    Book::Romans,
+    Book::FirstCorinthians,
+    Book::SecondCorinthians,
+    Book::Galatians,
+    Book::Ephesians,
+    Book::Philippians,
+    Book::Colossians,
+    Book::FirstThessalonians,
+    Book::SecondThessalonians,
+    Book::FirstTimothy,
+    Book::SecondTimothy,
+    Book::Titus,
+    Book::Philemon,
+    Book::Hebrews,
+    Book::James,
+    Book::FirstPeter,
+    Book::SecondPeter,
+    Book::FirstJohn,
+    Book::SecondJohn,
+    Book::ThirdJohn,
+    Book::Jude,
+    Book::Revelation,
+];
+
+impl Book {
+    /// Resolves raw book token (`"Gen"`, `"II Ki."`, `"Быт"`) into the book,
+    /// looking through all enabled languages
+    pub fn from_name(name: &str) -> Option<Book> {
+        names::lookup(name, None)
+    }
+
+    /// Resolves raw book token using only the given language
+    pub fn from_name_in(name: &str, language: Language) -> Option<Book> {
+        names::lookup(name, Some(language))
+    }
+
+    /// Zero-based position in the canon
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Number of chapters, following the KJV versification
+    pub fn chapters(self) -> u8 {
+        VERSE_COUNTS[self.index()].len() as u8
+    }
+
+    /// Whether the book has only one chapter and is cited by verse alone:
+    /// Obadiah, Philemon, 2 and 3 John, Jude
+    pub fn is_single_chapter(self) -> bool {
+        self.chapters() == 1
+    }
+
+    /// Number of verses in the chapter, following the KJV versification,
+    /// or `None` when the book has no such chapter
+    pub fn verses(self, chapter: u8) -> Option<u8> {
+        match chapter {
+            0 => None,
+            chapter => VERSE_COUNTS[self.index()]
+                .get(chapter as usize - 1)
+                .cloned(),
+        }
+    }
+
+    /// Full English name
+    pub fn name(self) -> &'static str {
+        match self {
+            Book::Genesis => "Genesis",
+            Book::Exodus => "Exodus",
+            Book::Leviticus => "Leviticus",
+            Book::Numbers => "Numbers",
+            Book::Deuteronomy => "Deuteronomy",
+            Book::Joshua => "Joshua",
+            Book::Judges => "Judges",
+            Book::Ruth => "Ruth",
+            Book::FirstSamuel => "1 Samuel",
+            Book::SecondSamuel => "2 Samuel",
+            Book::FirstKings => "1 Kings",
+            Book::SecondKings => "2 Kings",
+            Book::FirstChronicles => "1 Chronicles",
+            Book::SecondChronicles => "2 Chronicles",
+            Book::Ezra => "Ezra",
+            Book::Nehemiah => "Nehemiah",
+            Book::Esther => "Esther",
+            Book::Job => "Job",
+            Book::Psalms => "Psalms",
+            Book::Proverbs => "Proverbs",
+            Book::Ecclesiastes => "Ecclesiastes",
+            Book::SongOfSongs => "Song of Songs",
+            Book::Isaiah => "Isaiah",
+            Book::Jeremiah => "Jeremiah",
+            Book::Lamentations => "Lamentations",
+            Book::Ezekiel => "Ezekiel",
+            Book::Daniel => "Daniel",
+            Book::Hosea => "Hosea",
+            Book::Joel => "Joel",
+            Book::Amos => "Amos",
+            Book::Obadiah => "Obadiah",
+            Book::Jonah => "Jonah",
+            Book::Micah => "Micah",
+            Book::Nahum => "Nahum",
+            Book::Habakkuk => "Habakkuk",
+            Book::Zephaniah => "Zephaniah",
+            Book::Haggai => "Haggai",
+            Book::Zechariah => "Zechariah",
+            Book::Malachi => "Malachi",
+            Book::Matthew => "Matthew",
+            Book::Mark => "Mark",
+            Book::Luke => "Luke",
+            Book::John => "John",
+            Book::Acts => "Acts",
+            Book::Romans => "Romans",
+            Book::FirstCorinthians => "1 Corinthians",
+            Book::SecondCorinthians => "2 Corinthians",
+            Book::Galatians => "Galatians",
+            Book::Ephesians => "Ephesians",
+            Book::Philippians => "Philippians",
+            Book::Colossians => "Colossians",
+            Book::FirstThessalonians => "1 Thessalonians",
+            Book::SecondThessalonians => "2 Thessalonians",
+            Book::FirstTimothy => "1 Timothy",
+            Book::SecondTimothy => "2 Timothy",
+            Book::Titus => "Titus",
+            Book::Philemon => "Philemon",
+            Book::Hebrews => "Hebrews",
+            Book::James => "James",
+            Book::FirstPeter => "1 Peter",
+            Book::SecondPeter => "2 Peter",
+            Book::FirstJohn => "1 John",
+            Book::SecondJohn => "2 John",
+            Book::ThirdJohn => "3 John",
+            Book::Jude => "Jude",
+            Book::Revelation => "Revelation",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_canonical_order() {
+        for (index, book) in BOOKS.iter().enumerate() {
+            assert_eq!(book.index(), index);
+        }
+        assert!(Book::Genesis < Book::Revelation);
+        assert_eq!(Book::Matthew.name(), "Matthew");
+    }
+
+    #[test]
+    fn test_verse_counts() {
+        assert_eq!(Book::Genesis.chapters(), 50);
+        assert_eq!(Book::Psalms.chapters(), 150);
+        assert_eq!(Book::Jude.chapters(), 1);
+        assert_eq!(Book::Psalms.verses(119), Some(176));
+        assert_eq!(Book::John.verses(3), Some(36));
+        assert_eq!(Book::Jude.verses(2), None);
+        assert_eq!(Book::Jude.verses(0), None);
+    }
+
+    #[cfg(feature = "english")]
+    #[test]
+    fn test_from_name() {
+        assert_eq!(Book::from_name("Gen"), Some(Book::Genesis));
+        assert_eq!(Book::from_name("genesis"), Some(Book::Genesis));
+        assert_eq!(Book::from_name("II Ki."), Some(Book::SecondKings));
+        assert_eq!(Book::from_name("1Cor"), Some(Book::FirstCorinthians));
+        assert_eq!(Book::from_name("1 Cor."), Some(Book::FirstCorinthians));
+        assert_eq!(Book::from_name("Jh"), Some(Book::John));
+        assert_eq!(Book::from_name("Song of Solomon"), Some(Book::SongOfSongs));
+        assert_eq!(Book::from_name("Isa"), Some(Book::Isaiah));
+        assert_eq!(Book::from_name("Notes"), None);
+        assert_eq!(Book::from_name_in("Gen", Language::English), Some(Book::Genesis));
+    }
+}
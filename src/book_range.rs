@@ -0,0 +1,279 @@
+//! Whole books and ranges across books
+//!
+//! A book name alone cites the whole book (`Philemon`), and a range may run
+//! from one book into a later one (`Gen–Deut`, `Matt 27–Mark 2`). Many book
+//! names are ordinary words as well, so free text is only searched for them
+//! on request, see `ParseOptions::whole_books`.
+
+use regex::Regex;
+
+use typography::DASHES;
+use {
+    book_start, BibleReference, Book, BookEnd, Segment, Span, Typography, VersePoint, VerseRange,
+    BOOKS, BOOK_PATTERN,
+};
+
+/// Fewest letters of a name read as a whole book, so that words like `Is`,
+/// `Am` and `Ex` are left alone
+const WHOLE_BOOK_MIN_LETTERS: usize = 3;
+
+/// Adds references to known books named without chapters outside of the
+/// references found, keeping the references in text order. Only names
+/// starting with a capital letter are taken
+pub(crate) fn find_whole_books(string: &str, references: &mut Vec<BibleReference>) {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(BOOK_PATTERN).unwrap();
+    }
+
+    let mut books: Vec<BibleReference> = Vec::new();
+    for book in RE.captures_iter(string).filter_map(|m| m.name("Book")) {
+        let start = book_start(&string[..book.end()], book.start());
+        let name = &string[start..book.end()];
+        let book_id = match Book::from_name(name) {
+            Some(book_id) => book_id,
+            None => continue,
+        };
+        let mut letters = name.chars().filter(|c| c.is_alphabetic());
+        let capital = letters.next().is_some_and(char::is_uppercase);
+        if !capital || letters.count() + 1 < WHOLE_BOOK_MIN_LETTERS {
+            continue;
+        }
+        let span = Span {
+            start,
+            end: book.end(),
+        };
+        if references
+            .iter()
+            .any(|reference| span.start < reference.span.end && reference.span.start < span.end)
+        {
+            continue;
+        }
+
+        // "Song of Songs": the longer name replaces "Song" found before
+        while books.last().is_some_and(|last| last.span.start >= start) {
+            books.pop();
+        }
+        books.push(BibleReference {
+            book: name.to_string(),
+            book_id: Some(book_id),
+            locations: vec![],
+            segments: vec![],
+            span,
+            book_span: span,
+            location_spans: vec![],
+            inferred: false,
+            typography: Typography::default(),
+            book_end: None,
+        });
+    }
+
+    references.extend(books);
+    references.sort_by_key(|reference| reference.span.start);
+}
+
+/// Joins references separated only by a dash into ranges running from one
+/// book into a later one: `Gen–Deut`, `Matt 27–Mark 2`, `Matt 27:5–Mark 2:3`
+pub(crate) fn join_book_ranges(string: &str, references: &mut Vec<BibleReference>) {
+    let mut joined: Vec<BibleReference> = Vec::with_capacity(references.len());
+    for reference in references.drain(..) {
+        if let Some(last) = joined.last_mut() {
+            if let Some(end) = book_end(string, last, &reference) {
+                last.span.end = reference.span.end;
+                last.book_end = Some(end);
+                continue;
+            }
+        }
+        joined.push(reference);
+    }
+    *references = joined;
+}
+
+/// End of the range from `first` into the book of `next`, if they are
+/// written as one
+fn book_end(string: &str, first: &BibleReference, next: &BibleReference) -> Option<BookEnd> {
+    let single = |reference: &BibleReference| {
+        matches!(
+            reference.segments[..],
+            [] | [Segment::Chapter(_)] | [Segment::Verse(_)]
+        )
+    };
+    let (first_book, book) = (first.book_id?, next.book_id?);
+    if first.book_end.is_some() || !single(first) || !single(next) {
+        return None;
+    }
+    if book.index() <= first_book.index() {
+        return None;
+    }
+    let mut between = string.get(first.span.end..next.span.start)?.trim().chars();
+    match (between.next(), between.next()) {
+        (Some(dash), None) if DASHES.contains(&dash) => (),
+        _ => return None,
+    }
+
+    let (chapter, verse) = match next.segments.first() {
+        Some(&Segment::Chapter(chapter)) => (Some(chapter), None),
+        Some(&Segment::Verse(point)) => (Some(point.chapter), Some(point.verse)),
+        _ => (None, None),
+    };
+    Some(BookEnd {
+        book: next.book.clone(),
+        book_id: next.book_id,
+        chapter,
+        verse,
+        book_span: next.book_span,
+    })
+}
+
+impl BibleReference {
+    /// Whether the reference cites whole books without chapters:
+    /// `Philemon`, `Gen–Deut`
+    pub fn is_whole_book(&self) -> bool {
+        let chapter = self.book_end.as_ref().and_then(|end| end.chapter);
+        self.locations.is_empty() && chapter.is_none()
+    }
+
+    /// Segments of every book the reference covers, in canonical order,
+    /// with open ranges resolved. Whole books, and books a range runs
+    /// through, are given as all their chapters. Empty when a book is not resolved
+    pub(crate) fn book_segments(&self) -> Vec<(Book, Segment)> {
+        let whole = |book: Book| Segment::chapters(1, book.chapters());
+        let book = match self.book_id {
+            Some(book) => book,
+            None => return vec![],
+        };
+        let segments = self.resolved_segments();
+        let end = match self.book_end {
+            None if segments.is_empty() => return vec![(book, whole(book))],
+            None => {
+                return segments
+                    .into_iter()
+                    .map(|segment| (book, segment))
+                    .collect()
+            }
+            Some(ref end) => end,
+        };
+        let last = match end.book_id {
+            Some(last) => last,
+            None => return vec![],
+        };
+
+        let last_chapter = book.chapters();
+        let first = match segments.first() {
+            None => whole(book),
+            Some(&Segment::Chapter(chapter)) | Some(&Segment::Chapters(chapter, _)) => {
+                Segment::chapters(chapter, last_chapter)
+            }
+            Some(&Segment::Verse(start)) | Some(&Segment::Verses(VerseRange { start, .. })) => {
+                let end = VersePoint {
+                    chapter: last_chapter,
+                    verse: book.verses(last_chapter).unwrap_or(0),
+                };
+                Segment::verses(start, end)
+            }
+        };
+        let through = BOOKS[book.index() + 1..last.index()]
+            .iter()
+            .map(|&book| (book, whole(book)));
+        let end = match (end.chapter, end.verse) {
+            (None, _) => whole(last),
+            (Some(chapter), None) => Segment::chapters(1, chapter),
+            (Some(chapter), Some(verse)) => Segment::verses(
+                VersePoint {
+                    chapter: 1,
+                    verse: 1,
+                },
+                VersePoint { chapter, verse },
+            ),
+        };
+
+        let mut segments = vec![(book, first)];
+        segments.extend(through);
+        segments.push((last, end));
+        segments
+    }
+}
+
+#[cfg(all(test, feature = "english"))]
+mod tests {
+
+    use super::*;
+    use {parse, parse_with, ParseOptions, VerseId};
+
+    fn whole_books(text: &str) -> Vec<BibleReference> {
+        let options = ParseOptions {
+            whole_books: true,
+            ..ParseOptions::default()
+        };
+        parse_with(text, &options)
+    }
+
+    #[test]
+    fn test_whole_books() {
+        let text = "Read Philemon and Song of Songs, then Gen 1:1. Is it so?";
+        let refs = whole_books(text);
+        assert_eq!(refs.len(), 3);
+        assert_eq!(refs[0].book_id, Some(Book::Philemon));
+        assert!(refs[0].is_whole_book());
+        assert_eq!(refs[0].span.slice(text), "Philemon");
+        assert_eq!(refs[1].book_id, Some(Book::SongOfSongs));
+        assert_eq!(refs[1].span.slice(text), "Song of Songs");
+        assert_eq!(refs[2].book_id, Some(Book::Genesis));
+        assert!(!refs[2].is_whole_book());
+
+        assert_eq!(refs[0].iter_verses().count(), 25);
+        assert_eq!(refs[0].to_string(), "Phm");
+        assert!(parse(text).iter().all(|r| !r.is_whole_book()));
+    }
+
+    #[test]
+    fn test_book_ranges() {
+        let text = "Gen–Deut, Matt 27 – Mark 2 and Matt 28:19-Mark 1:3; Mark 2–Matt 1";
+        let refs = whole_books(text);
+        assert_eq!(refs.len(), 5);
+
+        let end = refs[0].book_end.as_ref().unwrap();
+        assert_eq!(end.book_id, Some(Book::Deuteronomy));
+        assert_eq!((end.chapter, end.verse), (None, None));
+        assert!(refs[0].is_whole_book());
+        assert_eq!(refs[0].span.slice(text), "Gen–Deut");
+        assert_eq!(end.book_span.slice(text), "Deut");
+
+        let end = refs[1].book_end.as_ref().unwrap();
+        assert_eq!(refs[1].locations[0].chapters, [27]);
+        assert_eq!(
+            (end.book_id, end.chapter, end.verse),
+            (Some(Book::Mark), Some(2), None)
+        );
+        assert_eq!(refs[1].span.slice(text), "Matt 27 – Mark 2");
+        assert_eq!(refs[1].to_string(), "Mt 27-Mk 2");
+
+        let end = refs[2].book_end.as_ref().unwrap();
+        assert_eq!((end.chapter, end.verse), (Some(1), Some(3)));
+        let id = |book, chapter, verse| VerseId::new(book, VersePoint { chapter, verse });
+        assert_eq!(
+            refs[2].id_ranges().collect::<Vec<_>>(),
+            [
+                (id(Book::Matthew, 28, 19), id(Book::Matthew, 28, 20)),
+                (id(Book::Mark, 1, 1), id(Book::Mark, 1, 3)),
+            ]
+        );
+        assert_eq!(refs[2].verse_ids().count(), 5);
+
+        // Backwards across books is not a range
+        assert!(refs[3].book_end.is_none());
+        assert_eq!(refs[3].book_id, Some(Book::Mark));
+        assert_eq!(refs[4].book_id, Some(Book::Matthew));
+
+        assert_eq!(refs[0].to_osis().unwrap(), "Gen-Deut");
+        assert_eq!(refs[2].to_osis().unwrap(), "Matt.28.19-Mark.1.3");
+        assert_eq!(refs[0].iter_verses().count(), 1533);
+        assert_eq!(refs[2].to_string(), "Mt 28:19-Mk 1:3");
+        let end = whole_books("Mt 28:19-Mk 1:3").remove(0).book_end.unwrap();
+        assert_eq!(
+            (end.book_id, end.chapter, end.verse),
+            (Some(Book::Mark), Some(1), Some(3))
+        );
+        assert!(refs.iter().all(|r| r.validate().is_ok()));
+        assert!(whole_books("Gen 50–Exod 41").remove(0).validate().is_err());
+    }
+}
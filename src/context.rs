@@ -0,0 +1,292 @@
+//! Context-relative references in running text
+//!
+//! Notes often refer back to the last cited passage: `Gen 1:1 … in v. 26`,
+//! `see ch. 3`, `ibid.`. Such markers are read by the main pattern as
+//! references with an unknown book, and here are resolved against the last
+//! reference with a known book.
+
+use regex::Regex;
+
+use segment::group;
+use {parse_with, BibleReference, ParseOptions, Segment, Span, Typography, VersePoint, VerseRange};
+
+/// What a relative marker refers to
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+enum Marker {
+    /// Verses of the current chapter: `v. 5`, `vv. 7–9`, `ст. 3`
+    Verse,
+    /// Chapters of the current book: `ch. 4`, `гл. 2`
+    Chapter,
+    /// Current book, or the whole previous reference when nothing follows:
+    /// `ibid.`, `там же`
+    Ibid,
+}
+
+#[rustfmt::skip]
+static VERSE_MARKERS: &[&str] = &[
+    "v", "vv", "vs", "vss", "ver", "verse", "verses", "vers", "ст", "стт", "стих", "стихи", "вірш",
+];
+
+#[rustfmt::skip]
+static CHAPTER_MARKERS: &[&str] = &[
+    "ch", "chs", "chap", "chapter", "chapters", "cap", "kap", "гл", "глава", "главы", "розд",
+];
+
+// ibid.
+// там же
+static IBID_PATTERN: &str = "(?i)\\b(ibid|ib|там[\\t\\f\\pZ]+же)\\b\\.?";
+
+/// Marker written as the book name of a parsed reference
+fn marker(name: &str) -> Option<Marker> {
+    let name = name.trim_end_matches('.').to_lowercase();
+    if VERSE_MARKERS.contains(&name.as_str()) {
+        Some(Marker::Verse)
+    } else if CHAPTER_MARKERS.contains(&name.as_str()) {
+        Some(Marker::Chapter)
+    } else if name == "ibid" || name == "ib" {
+        Some(Marker::Ibid)
+    } else {
+        None
+    }
+}
+
+/// Parses a whole document, resolving relative markers (`v.`, `vv.`, `ch.`,
+/// `ст.`, `гл.`, `ibid.`, `там же`) against the last preceding reference with
+/// a known book. Resolved references are flagged as `inferred` and their
+/// `book_span` covers the marker. Markers with nothing to refer to are dropped
+pub fn parse_document(string: &str, options: &ParseOptions) -> Vec<BibleReference> {
+    lazy_static! {
+        static ref IBID: Regex = Regex::new(IBID_PATTERN).unwrap();
+    }
+
+    let mut ibids = IBID
+        .find_iter(string)
+        .map(|m| Span {
+            start: m.start(),
+            end: m.end(),
+        }).peekable();
+    let mut context: Option<BibleReference> = None;
+    let mut references = Vec::new();
+
+    for reference in parse_with(string, options) {
+        while let Some(&ibid) = ibids.peek() {
+            if ibid.end > reference.book_span.start {
+                break;
+            }
+            ibids.next();
+            if let Some(ref last) = context {
+                references.push(repeat(last, ibid));
+            }
+        }
+
+        let attached = match ibids.peek() {
+            Some(&ibid) if ibid.start <= reference.book_span.start => Some(ibid),
+            _ => None,
+        };
+        let (marker, marker_span) = match attached {
+            Some(ibid) => {
+                ibids.next();
+                (Some(Marker::Ibid), ibid)
+            }
+            None => {
+                let word = reference.book.split_whitespace().last().unwrap_or("");
+                let span = Span {
+                    start: reference.book_span.end - word.len(),
+                    end: reference.book_span.end,
+                };
+                match reference.book_id {
+                    Some(_) => (None, span),
+                    None => (marker(word), span),
+                }
+            }
+        };
+
+        let reference = match marker {
+            None => reference,
+            Some(marker) => match context
+                .as_ref()
+                .and_then(|last| infer(reference, marker, marker_span, last))
+            {
+                Some(reference) => reference,
+                None => continue,
+            },
+        };
+        if reference.book_id.is_some() {
+            context = Some(reference.clone());
+        }
+        references.push(reference);
+    }
+
+    for ibid in ibids {
+        if let Some(ref last) = context {
+            references.push(repeat(last, ibid));
+        }
+    }
+    references
+}
+
+/// Previous reference repeated by a standalone `ibid.`
+fn repeat(last: &BibleReference, span: Span) -> BibleReference {
+    BibleReference {
+        span,
+        book_span: span,
+        location_spans: vec![],
+        inferred: true,
+        typography: Typography::default(),
+        ..last.clone()
+    }
+}
+
+/// Resolves locations written after a marker against the previous reference
+fn infer(
+    reference: BibleReference,
+    marker: Marker,
+    marker_span: Span,
+    last: &BibleReference,
+) -> Option<BibleReference> {
+    let (locations, segments, location_spans) = if marker == Marker::Verse {
+        let chapter = last_chapter(last)?;
+        let segments: Vec<(Segment, Span)> = reference
+            .locations
+            .iter()
+            .zip(&reference.location_spans)
+            .flat_map(|(location, &span)| {
+                location
+                    .segments()
+                    .into_iter()
+                    .map(move |segment| (in_chapter(segment, chapter), span))
+            }).collect();
+        let (locations, location_spans) = group(&segments).into_iter().unzip();
+        let segments = segments.into_iter().map(|(segment, _)| segment).collect();
+        (locations, segments, location_spans)
+    } else {
+        (
+            reference.locations,
+            reference.segments,
+            reference.location_spans,
+        )
+    };
+
+    Some(BibleReference {
+        book: last.book.clone(),
+        book_id: last.book_id,
+        locations,
+        segments,
+        span: Span {
+            start: marker_span.start,
+            end: reference.span.end,
+        },
+        book_span: marker_span,
+        location_spans,
+        inferred: true,
+        typography: reference.typography,
+        book_end: None,
+    })
+}
+
+/// Chapter the reference ends in
+fn last_chapter(reference: &BibleReference) -> Option<u8> {
+    reference.segments.last().map(|&segment| match segment {
+        Segment::Chapter(chapter) | Segment::Chapters(_, chapter) => chapter,
+        Segment::Verse(point) => point.chapter,
+        Segment::Verses(range) => range.end.chapter,
+    })
+}
+
+/// Reads numbers parsed as chapters as verses of the chapter
+fn in_chapter(segment: Segment, chapter: u8) -> Segment {
+    let point = |verse| VersePoint { chapter, verse };
+    match segment {
+        Segment::Chapter(verse) => Segment::Verse(point(verse)),
+        Segment::Chapters(first, last) => Segment::Verses(VerseRange {
+            start: point(first),
+            end: point(last),
+        }),
+        segment => segment,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    #[cfg(any(feature = "english", feature = "russian"))]
+    use Book;
+
+    fn document(text: &str) -> Vec<BibleReference> {
+        parse_document(text, &ParseOptions::default())
+    }
+
+    #[cfg(feature = "english")]
+    #[test]
+    fn test_verse_markers() {
+        let text = "Gen 1:1 speaks of creation, and in v. 26 of man; see also vv. 27–28.";
+        let refs = document(text);
+        assert_eq!(refs.len(), 3);
+        assert!(!refs[0].inferred);
+
+        assert!(refs[1].inferred);
+        assert_eq!(refs[1].book_id, Some(Book::Genesis));
+        assert_eq!(refs[1].book, "Gen");
+        assert_eq!(refs[1].locations[0].chapters, [1]);
+        assert_eq!(refs[1].locations[0].verses, Some(vec![26]));
+        assert_eq!(refs[1].span.slice(text), "v. 26");
+        assert_eq!(refs[1].book_span.slice(text), "v.");
+
+        assert_eq!(refs[2].locations[0].verses, Some(vec![27, 28]));
+        assert_eq!(
+            refs[2].segments,
+            [Segment::Verses(VerseRange {
+                start: VersePoint {
+                    chapter: 1,
+                    verse: 27
+                },
+                end: VersePoint {
+                    chapter: 1,
+                    verse: 28
+                },
+            })]
+        );
+        assert_eq!(refs[2].location_spans[0].slice(text), "27–28");
+    }
+
+    #[cfg(feature = "russian")]
+    #[test]
+    fn test_chapter_markers() {
+        let refs = document("Быт 1:1 … гл. 3 … ст. 15");
+        assert_eq!(refs.len(), 3);
+        assert_eq!(refs[1].book_id, Some(Book::Genesis));
+        assert_eq!(refs[1].locations[0].chapters, [3]);
+        assert_eq!(refs[1].locations[0].verses, None);
+        assert_eq!(refs[2].locations[0].chapters, [3]);
+        assert_eq!(refs[2].locations[0].verses, Some(vec![15]));
+    }
+
+    #[cfg(feature = "english")]
+    #[test]
+    fn test_ibid() {
+        let text = "John 3:16. Ibid. 4:2; cf. ibid. Там же 5";
+        let refs = document(text);
+        assert_eq!(refs.len(), 4);
+        assert!(refs.iter().skip(1).all(|r| r.inferred));
+        assert!(refs.iter().all(|r| r.book_id == Some(Book::John)));
+
+        assert_eq!(refs[1].span.slice(text), "Ibid. 4:2");
+        assert_eq!(refs[1].locations[0].chapters, [4]);
+        assert_eq!(refs[2].span.slice(text), "ibid.");
+        assert_eq!(refs[2].locations[0].chapters, [4]);
+        assert_eq!(refs[2].locations[0].verses, Some(vec![2]));
+        assert_eq!(refs[3].span.slice(text), "Там же 5");
+        assert_eq!(refs[3].locations[0].chapters, [5]);
+    }
+
+    #[test]
+    fn test_without_context() {
+        assert!(document("see v. 5 and ibid.").is_empty());
+
+        let refs = document("Notes 3, v. 5");
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].book, "Notes");
+        assert!(!refs[0].inferred);
+    }
+}
@@ -0,0 +1,108 @@
+//! Errors of strict reference parsing and validation
+
+use std::error::Error;
+use std::fmt;
+
+use {Book, Span};
+
+/// Reason why a single reference could not be parsed
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+pub enum ParseError {
+    /// Input is empty
+    Empty,
+    /// Book name is missing or not recognised
+    UnknownBook(String),
+    /// Book name is not followed by chapter or verse numbers
+    MissingLocation,
+    /// Chapter or verse number is zero or doesn't fit into `u8`
+    NumberOutOfRange(Span),
+    /// Range ends before it starts
+    DescendingRange(Span),
+    /// Unexpected text, including anything after the reference
+    UnexpectedText(Span),
+}
+
+impl ParseError {
+    /// Moves error position by `offset` bytes
+    pub(crate) fn shift(self, offset: usize) -> ParseError {
+        match self {
+            ParseError::NumberOutOfRange(span) => ParseError::NumberOutOfRange(span.shift(offset)),
+            ParseError::DescendingRange(span) => ParseError::DescendingRange(span.shift(offset)),
+            ParseError::UnexpectedText(span) => ParseError::UnexpectedText(span.shift(offset)),
+            error => error,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::Empty => write!(f, "empty reference"),
+            ParseError::UnknownBook(ref book) => write!(f, "unknown book `{}`", book),
+            ParseError::MissingLocation => write!(f, "missing chapter or verse"),
+            ParseError::NumberOutOfRange(span) => {
+                write!(f, "number out of range at {}..{}", span.start, span.end)
+            }
+            ParseError::DescendingRange(span) => {
+                write!(f, "descending range at {}..{}", span.start, span.end)
+            }
+            ParseError::UnexpectedText(span) => {
+                write!(f, "unexpected text at {}..{}", span.start, span.end)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Location that doesn't exist in the book
+#[derive(Hash, Eq, PartialEq, Debug, Clone, Copy)]
+pub enum ValidationError {
+    /// Book name is not resolved, so its bounds are unknown
+    UnknownBook,
+    /// Chapter is zero or past the last chapter of the book
+    ChapterOutOfBounds {
+        /// Book
+        book: Book,
+        /// Chapter referenced
+        chapter: u8,
+    },
+    /// Verse is zero or past the last verse of the chapter
+    VerseOutOfBounds {
+        /// Book
+        book: Book,
+        /// Chapter referenced
+        chapter: u8,
+        /// Verse referenced
+        verse: u8,
+    },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ValidationError::UnknownBook => write!(f, "unknown book"),
+            ValidationError::ChapterOutOfBounds { book, chapter } => write!(
+                f,
+                "{} has no chapter {}, only {}",
+                book.name(),
+                chapter,
+                book.chapters()
+            ),
+            ValidationError::VerseOutOfBounds {
+                book,
+                chapter,
+                verse,
+            } => write!(
+                f,
+                "{} {} has no verse {}, only {}",
+                book.name(),
+                chapter,
+                verse,
+                book.verses(chapter).unwrap_or(0)
+            ),
+        }
+    }
+}
+
+impl Error for ValidationError {}
@@ -0,0 +1,492 @@
+//! Formatting references back into text
+//!
+//! Formatted text is always accepted by `parse` and gives back the same
+//! book and locations, so references can be stored as plain strings. Whole
+//! books and ranges across books need `ParseOptions::whole_books`.
+
+use std::fmt;
+
+use names::lookup;
+use segment::runs;
+use {
+    BibleReference, Book, BookEnd, Language, OpenEnd, OpenRange, VerseLocation, VersePart,
+    VersePoint, LANGUAGES,
+};
+
+/// How the book name is written
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum BookStyle {
+    /// Book token as it was written in the parsed text
+    AsWritten,
+    /// Shortest abbreviation (`Ps`)
+    Short,
+    /// Full name (`Psalms`)
+    Full,
+    /// Society of Biblical Literature abbreviation (`Ps`, `1 Kgs`, `Phlm`)
+    Sbl,
+}
+
+/// Formatting options
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct Format {
+    /// Book name style
+    pub book: BookStyle,
+    /// Language of `Short` and `Full` names. When `None`, the language the
+    /// book was written in is used
+    pub language: Option<Language>,
+    /// Use thin spaces (U+2009) instead of regular ones
+    pub thin_space: bool,
+    /// Use en dashes (U+2013) instead of hyphens in ranges
+    pub en_dash: bool,
+    /// Keep letter suffixes of verses cited in part (`2a`). When off they
+    /// are written as whole verses
+    pub verse_parts: bool,
+}
+
+impl Format {
+    /// Shortest abbreviation with plain spaces and hyphens (`Ps 23:1-3`)
+    pub fn short() -> Format {
+        Format {
+            book: BookStyle::Short,
+            language: None,
+            thin_space: false,
+            en_dash: false,
+            verse_parts: true,
+        }
+    }
+
+    /// Full name with plain spaces and hyphens (`Psalms 23:1-3`)
+    pub fn full() -> Format {
+        Format {
+            book: BookStyle::Full,
+            ..Format::short()
+        }
+    }
+
+    /// SBL abbreviation with en dashes (`Ps 23:1–3`)
+    pub fn sbl() -> Format {
+        Format {
+            book: BookStyle::Sbl,
+            en_dash: true,
+            ..Format::short()
+        }
+    }
+}
+
+impl Default for Format {
+    fn default() -> Format {
+        Format::short()
+    }
+}
+
+impl BibleReference {
+    /// Formats reference as text using the given options
+    pub fn format(&self, format: &Format) -> String {
+        let space = if format.thin_space { "\u{2009}" } else { " " };
+        let dash = if format.en_dash { "–" } else { "-" };
+
+        let locations: Vec<String> = self
+            .locations
+            .iter()
+            .map(|location| {
+                let parts: &[VersePart] = if format.verse_parts {
+                    &location.parts
+                } else {
+                    &[]
+                };
+                match single_chapter(location, self.book_id) {
+                    Some(ref verses) => format_location(verses, parts, dash, space),
+                    None => format_location(location, parts, dash, space),
+                }
+            }).collect();
+        let book = book_name(&self.book, self.book_id, format).replace(' ', space);
+        let mut start = book;
+        for (index, text) in locations.iter().enumerate() {
+            if index > 0 {
+                // "Ge 1:1; 3", "Ge 1; 2": after a comma a lone number would
+                // be read as a verse or as a chapter of the same location
+                let chapters = is_chapters(&self.locations[index]);
+                start.push(if chapters { ';' } else { ',' });
+            }
+            start.push_str(space);
+            start.push_str(text);
+        }
+        match self.book_end {
+            Some(ref end) => format!("{}{}{}", start, dash, format_book_end(end, format, space)),
+            None => start,
+        }
+    }
+}
+
+impl fmt::Display for BibleReference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.format(&Format::default()))
+    }
+}
+
+/// Book name in the requested style, falling back to the name as written
+fn book_name(written: &str, book: Option<Book>, format: &Format) -> String {
+    let book = match book {
+        Some(book) => book,
+        None => return written.to_string(),
+    };
+
+    let language = format.language.or_else(|| {
+        LANGUAGES
+            .iter()
+            .cloned()
+            .find(|&language| lookup(written, Some(language)) == Some(book))
+    });
+    // Only names resolving back to the same book keep the round trip
+    let names: Vec<&str> = language
+        .map_or(&[][..], |language| language.names(book))
+        .iter()
+        .cloned()
+        .filter(|name| lookup(name, None) == Some(book))
+        .collect();
+
+    let name = match format.book {
+        BookStyle::AsWritten => None,
+        BookStyle::Full => names.first().cloned(),
+        BookStyle::Short => names
+            .iter()
+            .cloned()
+            .min_by_key(|name| name.chars().count()),
+        BookStyle::Sbl => Some(sbl_name(book)).filter(|name| lookup(name, None) == Some(book)),
+    };
+    name.map_or_else(|| written.to_string(), String::from)
+}
+
+/// Whole chapter of a book with one chapter written as its verses
+/// (`Jude 1:1-25`), since a lone number there is read as a verse
+fn single_chapter(location: &VerseLocation, book: Option<Book>) -> Option<VerseLocation> {
+    let book = book.filter(|&book| book.is_single_chapter())?;
+    if location.chapters != [1] || location.verses.is_some() || location.range.is_some() {
+        return None;
+    }
+    Some(VerseLocation {
+        verses: Some((1..=book.verses(1)?).collect()),
+        ..location.clone()
+    })
+}
+
+/// Whether the location cites whole chapters only
+fn is_chapters(location: &VerseLocation) -> bool {
+    location.verses.is_none() && location.range.is_none()
+}
+
+/// Last book of a range across books, with its last chapter and verse
+fn format_book_end(end: &BookEnd, format: &Format, space: &str) -> String {
+    let book = book_name(&end.book, end.book_id, format).replace(' ', space);
+    match (end.chapter, end.verse) {
+        (Some(chapter), Some(verse)) => format!("{}{}{}:{}", book, space, chapter, verse),
+        (Some(chapter), None) => format!("{}{}{}", book, space, chapter),
+        (None, _) => book,
+    }
+}
+
+fn format_location(
+    location: &VerseLocation,
+    parts: &[VersePart],
+    dash: &str,
+    space: &str,
+) -> String {
+    if let Some(range) = location.range {
+        let verse = |point: VersePoint| {
+            let part = parts.iter().find(|part| part.point == point);
+            with_part(point.verse, part.map(|part| part.part))
+        };
+        return format!(
+            "{}:{}{}{}:{}",
+            range.start.chapter,
+            verse(range.start),
+            dash,
+            range.end.chapter,
+            verse(range.end)
+        );
+    }
+
+    let chapters = collapse(&location.chapters, dash, space);
+    match location.verses {
+        Some(ref verses) => format!(
+            "{}:{}",
+            chapters,
+            collapse_verses(verses, parts, &location.open_ranges, dash, space)
+        ),
+        None => chapters,
+    }
+}
+
+/// Joins numbers collapsing consecutive runs into ranges (`5–8, 10`)
+fn collapse(numbers: &[u8], dash: &str, space: &str) -> String {
+    let parts: Vec<String> = runs(numbers)
+        .into_iter()
+        .map(|(first, last)| {
+            if first == last {
+                first.to_string()
+            } else {
+                format!("{}{}{}", first, dash, last)
+            }
+        }).collect();
+    parts.join(&format!(",{}", space))
+}
+
+/// Joins verses like `collapse`, with their part suffixes and open ends.
+/// A verse cited in part only starts or ends a range (`1b-3a, 4`), and a
+/// verse with an open end stands alone (`1, 2ff.`)
+fn collapse_verses(
+    verses: &[u8],
+    parts: &[VersePart],
+    open_ranges: &[OpenRange],
+    dash: &str,
+    space: &str,
+) -> String {
+    let mut pending = parts.iter().peekable();
+    let mut pending_open = open_ranges.iter().peekable();
+    let verses: Vec<(u8, Option<char>, Option<OpenEnd>)> = verses
+        .iter()
+        .map(|&verse| {
+            let part = match pending.peek() {
+                Some(&&part) if part.point.verse == verse => {
+                    pending.next();
+                    Some(part.part)
+                }
+                _ => None,
+            };
+            let open = match pending_open.peek() {
+                Some(&&open) if open.start.verse == verse => {
+                    pending_open.next();
+                    Some(open.end)
+                }
+                _ => None,
+            };
+            (verse, part, open)
+        }).collect();
+
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    for (index, &(verse, _, open)) in verses.iter().enumerate() {
+        match runs.last_mut() {
+            Some(&mut (first, ref mut last))
+                if u16::from(verses[*last].0) + 1 == u16::from(verse)
+                    && (verses[*last].1.is_none() || *last == first)
+                    && verses[*last].2.is_none()
+                    && open.is_none() =>
+            {
+                *last = index
+            }
+            _ => runs.push((index, index)),
+        }
+    }
+
+    let verse = |(verse, part, open): (u8, Option<char>, Option<OpenEnd>)| {
+        let open = match open {
+            Some(OpenEnd::Following) => "f.".to_string(),
+            Some(OpenEnd::FollowingVerses) => "ff.".to_string(),
+            Some(OpenEnd::End) => format!("{}end", dash),
+            None => String::new(),
+        };
+        format!("{}{}", with_part(verse, part), open)
+    };
+    let text: Vec<String> = runs
+        .into_iter()
+        .map(|(first, last)| {
+            if first == last {
+                verse(verses[first])
+            } else {
+                format!("{}{}{}", verse(verses[first]), dash, verse(verses[last]))
+            }
+        }).collect();
+    text.join(&format!(",{}", space))
+}
+
+/// Verse number with its part suffix
+fn with_part(verse: u8, part: Option<char>) -> String {
+    match part {
+        Some(part) => format!("{}{}", verse, part),
+        None => verse.to_string(),
+    }
+}
+
+/// SBL Handbook of Style abbreviation
+fn sbl_name(book: Book) -> &'static str {
+    match book {
+        Book::Genesis => "Gen",
+        Book::Exodus => "Exod",
+        Book::Leviticus => "Lev",
+        Book::Numbers => "Num",
+        Book::Deuteronomy => "Deut",
+        Book::Joshua => "Josh",
+        Book::Judges => "Judg",
+        Book::Ruth => "Ruth",
+        Book::FirstSamuel => "1 Sam",
+        Book::SecondSamuel => "2 Sam",
+        Book::FirstKings => "1 Kgs",
+        Book::SecondKings => "2 Kgs",
+        Book::FirstChronicles => "1 Chr",
+        Book::SecondChronicles => "2 Chr",
+        Book::Ezra => "Ezra",
+        Book::Nehemiah => "Neh",
+        Book::Esther => "Esth",
+        Book::Job => "Job",
+        Book::Psalms => "Ps",
+        Book::Proverbs => "Prov",
+        Book::Ecclesiastes => "Eccl",
+        Book::SongOfSongs => "Song",
+        Book::Isaiah => "Isa",
+        Book::Jeremiah => "Jer",
+        Book::Lamentations => "Lam",
+        Book::Ezekiel => "Ezek",
+        Book::Daniel => "Dan",
+        Book::Hosea => "Hos",
+        Book::Joel => "Joel",
+        Book::Amos => "Amos",
+        Book::Obadiah => "Obad",
+        Book::Jonah => "Jonah",
+        Book::Micah => "Mic",
+        Book::Nahum => "Nah",
+        Book::Habakkuk => "Hab",
+        Book::Zephaniah => "Zeph",
+        Book::Haggai => "Hag",
+        Book::Zechariah => "Zech",
+        Book::Malachi => "Mal",
+        Book::Matthew => "Matt",
+        Book::Mark => "Mark",
+        Book::Luke => "Luke",
+        Book::John => "John",
+        Book::Acts => "Acts",
+        Book::Romans => "Rom",
+        Book::FirstCorinthians => "1 Cor",
+        Book::SecondCorinthians => "2 Cor",
+        Book::Galatians => "Gal",
+        Book::Ephesians => "Eph",
+        Book::Philippians => "Phil",
+        Book::Colossians => "Col",
+        Book::FirstThessalonians => "1 Thess",
+        Book::SecondThessalonians => "2 Thess",
+        Book::FirstTimothy => "1 Tim",
+        Book::SecondTimothy => "2 Tim",
+        Book::Titus => "Titus",
+        Book::Philemon => "Phlm",
+        Book::Hebrews => "Heb",
+        Book::James => "Jas",
+        Book::FirstPeter => "1 Pet",
+        Book::SecondPeter => "2 Pet",
+        Book::FirstJohn => "1 John",
+        Book::SecondJohn => "2 John",
+        Book::ThirdJohn => "3 John",
+        Book::Jude => "Jude",
+        Book::Revelation => "Rev",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    #[cfg(any(feature = "english", feature = "russian"))]
+    use parse;
+    #[cfg(feature = "english")]
+    use BOOKS;
+
+    #[cfg(any(feature = "english", feature = "russian"))]
+    fn round_trip(text: &str, format: &Format) -> String {
+        let reference = parse(text).remove(0);
+        let formatted = reference.format(format);
+        let parsed = parse(&formatted);
+        assert_eq!(parsed.len(), 1, "{}", formatted);
+        assert_eq!(parsed[0].book_id, reference.book_id, "{}", formatted);
+        assert_eq!(parsed[0].locations, reference.locations, "{}", formatted);
+        formatted
+    }
+
+    #[test]
+    fn test_collapse() {
+        assert_eq!(collapse(&[5, 6, 7, 8, 10], "–", " "), "5–8, 10");
+        assert_eq!(collapse(&[1, 3, 4], "-", " "), "1, 3-4");
+        assert_eq!(collapse(&[7], "-", " "), "7");
+        assert_eq!(collapse(&[254, 255], "-", " "), "254-255");
+    }
+
+    #[cfg(feature = "english")]
+    #[test]
+    fn test_sbl_names_resolve() {
+        for book in BOOKS.iter() {
+            assert_eq!(lookup(sbl_name(*book), None), Some(*book));
+        }
+    }
+
+    #[cfg(feature = "english")]
+    #[test]
+    fn test_format() {
+        let sbl = Format::sbl();
+        assert_eq!(round_trip("Gen 5-8, 10", &sbl), "Gen 5–8, 10");
+        assert_eq!(round_trip("1 Kings 3:4-6, 9", &sbl), "1 Kgs 3:4–6, 9");
+        assert_eq!(round_trip("Song of Songs 1:30-2:3", &sbl), "Song 1:30–2:3");
+        assert_eq!(
+            round_trip("Psalm 23:1-3, 5:2", &Format::full()),
+            "Psalms 23:1-3, 5:2"
+        );
+        assert_eq!(round_trip("Psalm 23", &Format::short()), "Ps 23");
+        assert_eq!(
+            round_trip(
+                "Jn 3:16",
+                &Format {
+                    thin_space: true,
+                    ..sbl
+                }
+            ),
+            "John\u{2009}3:16"
+        );
+        assert_eq!(round_trip("Notes 1:2-4", &Format::full()), "Notes 1:2-4");
+        assert_eq!(parse("Gen 1:1, 3").remove(0).to_string(), "Ge 1:1, 3");
+        assert_eq!(round_trip("Gen 1:1 3", &Format::short()), "Ge 1:1; 3");
+        assert_eq!(
+            round_trip("Gen 1:30-2:3 5, 7:2 9", &Format::short()),
+            "Ge 1:30-2:3; 5, 7:2; 9"
+        );
+        assert_eq!(round_trip("Gen 3 4:1", &Format::short()), "Ge 3, 4:1");
+        assert_eq!(round_trip("Gen 1 2", &Format::short()), "Ge 1; 2");
+        assert_eq!(round_trip("Ps 23 1 5:2", &Format::short()), "Ps 23; 1, 5:2");
+        assert_eq!(round_trip("Gen 1, 2 4", &Format::short()), "Ge 1-2; 4");
+    }
+
+    #[cfg(feature = "english")]
+    #[test]
+    fn test_format_verse_parts() {
+        let short = Format::short();
+        assert_eq!(round_trip("Ps 23:1b-3a", &short), "Ps 23:1b-3a");
+        assert_eq!(round_trip("Gen 1:1-2a, 3, 5b", &short), "Ge 1:1-2a, 3, 5b");
+        assert_eq!(round_trip("Gen 1:30b-2:3a", &short), "Ge 1:30b-2:3a");
+
+        let whole = Format {
+            verse_parts: false,
+            ..short
+        };
+        let reference = parse("Ps 23:1b-3a, 5b").remove(0);
+        assert_eq!(reference.format(&whole), "Ps 23:1-3, 5");
+        assert_eq!(reference.to_whole_verses().to_string(), "Ps 23:1-3, 5");
+    }
+
+    #[cfg(feature = "english")]
+    #[test]
+    fn test_format_open_ranges() {
+        let short = Format::short();
+        assert_eq!(round_trip("Rom 8:28ff.", &short), "Ro 8:28ff.");
+        assert_eq!(round_trip("Gen 12:1f., 5", &short), "Ge 12:1f., 5");
+        assert_eq!(round_trip("Gen 12:1, 2ff.", &short), "Ge 12:1, 2ff.");
+        assert_eq!(
+            round_trip("Ps 119:169-end", &Format::sbl()),
+            "Ps 119:169–end"
+        );
+    }
+
+    #[cfg(feature = "russian")]
+    #[test]
+    fn test_format_language() {
+        assert_eq!(
+            round_trip("Быт 1:1-5, 7", &Format::full()),
+            "Бытие 1:1-5, 7"
+        );
+        assert_eq!(round_trip("Ин 3:16", &Format::short()), "Ин 3:16");
+    }
+}
@@ -6,12 +6,15 @@
 //! #Examples
 //!
 //! ```rust
+//! # #[cfg(feature = "english")] {
 //! let refs = bible_reference_rs::parse("Gen 1:1-3, Act 9");
 //!
 //! assert_eq!(refs.len(), 2);
 //! assert_eq!(refs[0].book, "Gen");
+//! assert_eq!(refs[0].book_id, Some(bible_reference_rs::Book::Genesis));
 //! assert_eq!(refs[0].locations[0].chapters, [1]);
 //! assert_eq!(refs[0].locations[0].verses, Some(vec![1, 2, 3]));
+//! # }
 //! ```
 
 #![deny(missing_docs)]
@@ -20,8 +23,96 @@
 extern crate lazy_static;
 extern crate regex;
 
+mod book;
+mod book_range;
+mod context;
+mod data;
+mod error;
+mod format;
+mod locations;
+mod names;
+mod notation;
+mod open_end;
+mod order;
+mod osis;
+mod segment;
+mod set;
+mod single_chapter;
+mod span;
+mod typography;
+mod validate;
+mod verse_id;
+mod versification;
+
+pub use book::{Book, BOOKS};
+pub use context::parse_document;
+pub use error::{ParseError, ValidationError};
+pub use format::{BookStyle, Format};
+pub use names::{Language, LANGUAGES};
+pub use notation::Notation;
+pub use order::merge;
+pub use osis::parse_osis;
+pub use segment::Segment;
+pub use set::VerseSet;
+pub use span::{Position, Span};
+pub use typography::Typography;
+pub use verse_id::VerseId;
+pub use versification::Versification;
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use locations::{parse_locations, parse_locations_strict};
 use regex::Regex;
 
+/// Chapter and verse pair
+#[derive(Hash, Eq, PartialEq, Ord, PartialOrd, Debug, Clone, Copy)]
+pub struct VersePoint {
+    /// Chapter
+    pub chapter: u8,
+    /// Verse
+    pub verse: u8,
+}
+
+/// Inclusive verse range, possibly crossing chapter boundary
+#[derive(Hash, Eq, PartialEq, Debug, Clone, Copy)]
+pub struct VerseRange {
+    /// First verse
+    pub start: VersePoint,
+    /// Last verse
+    pub end: VersePoint,
+}
+
+/// Verse cited in part, marked with a letter suffix: `2a`, `3b`
+#[derive(Hash, Eq, PartialEq, Debug, Clone, Copy)]
+pub struct VersePart {
+    /// Verse
+    pub point: VersePoint,
+    /// Suffix letter
+    pub part: char,
+}
+
+/// How a range written without its last verse ends
+#[derive(Hash, Eq, PartialEq, Ord, PartialOrd, Debug, Clone, Copy)]
+pub enum OpenEnd {
+    /// With the following verse: `12:1f.`
+    Following,
+    /// With the following verses, to the end of the chapter: `8:28ff.`, `8:28 сл.`
+    FollowingVerses,
+    /// To the end of the chapter: `119:169-end`
+    End,
+}
+
+/// Range written without its last verse
+#[derive(Hash, Eq, PartialEq, Ord, PartialOrd, Debug, Clone, Copy)]
+pub struct OpenRange {
+    /// First verse
+    pub start: VersePoint,
+    /// How the range ends
+    pub end: OpenEnd,
+}
+
 /// Verse location representation
 #[derive(Hash, Eq, PartialEq, Debug, Clone)]
 pub struct VerseLocation {
@@ -29,6 +120,31 @@ pub struct VerseLocation {
     pub chapters: Vec<u8>,
     /// Verses
     pub verses: Option<Vec<u8>>,
+    /// Range crossing chapter boundary (`1:30-2:3`). When set, `chapters`
+    /// lists every chapter it touches and `verses` is `None`
+    pub range: Option<VerseRange>,
+    /// Verses of `verses` or `range` cited in part, in source order:
+    /// `1:2a`, `1:1b-3a`. Empty when only whole verses are cited
+    pub parts: Vec<VersePart>,
+    /// Verses of `verses` starting a range with an open end (`8:28ff.`).
+    /// Until `resolve_open_ranges` is called only the first verse is listed
+    pub open_ranges: Vec<OpenRange>,
+}
+
+/// Last book of a reference running into other books: `Deut` in
+/// `Gen–Deut`, `Mark 2` in `Matt 27–Mark 2`
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+pub struct BookEnd {
+    /// Book name
+    pub book: String,
+    /// Resolved book, if the name is known
+    pub book_id: Option<Book>,
+    /// Last chapter, `None` when the range runs to the end of the book
+    pub chapter: Option<u8>,
+    /// Last verse of `chapter`, `None` when the range runs to its end
+    pub verse: Option<u8>,
+    /// Book name in the parsed text
+    pub book_span: Span,
 }
 
 /// Verse reference representation
@@ -36,120 +152,460 @@ pub struct VerseLocation {
 pub struct BibleReference {
     /// Book name
     pub book: String,
+    /// Resolved book, if the name is known
+    pub book_id: Option<Book>,
     /// Verse locations
     pub locations: Vec<VerseLocation>,
+    /// Chapters and verses as written, with ranges kept compact.
+    /// `locations` is the expanded view of the same segments
+    pub segments: Vec<Segment>,
+    /// Whole reference in the parsed text
+    pub span: Span,
+    /// Book name in the parsed text
+    pub book_span: Span,
+    /// Every location in the parsed text, in the order of `locations`,
+    /// empty for references that were not parsed
+    pub location_spans: Vec<Span>,
+    /// Whether the book or chapter was taken from the preceding text
+    /// rather than written (`v. 5`, `ibid.`), see `parse_document`
+    pub inferred: bool,
+    /// Dashes, spaces and full-width forms the reference was written with
+    pub typography: Typography,
+    /// Book the reference runs to (`Gen–Deut`, `Matt 27–Mark 2`), `None`
+    /// within one book. A reference without `locations` starts at the
+    /// beginning of its book, and alone cites the whole book (`Philemon`)
+    pub book_end: Option<BookEnd>,
 }
 
-// Single chapter: 1
-// Range: 1-2
-// Sequence: 1,4
-// Mixed chapters 1-2,4
-// Single verse: 1:1
-// Range: 1:1-3
-// Sequence: 1:1,3
-// Mixed verses: 1:1-2,4
-static VERSES_LOCATION_PATTERN: &'static str = "(?P<Chapter>1?[0-9]?[0-9])\
-                                                (-(?P<ChapterEnd>\\d+)|,\\s*(?P<ChapterNext>\\d+))*\
-                                                (:\\s*(?P<Verse>\\d+))?\
-                                                (-(?P<VerseEnd>\\d+)|,\\s*(?P<VerseNext>\\d+))*";
+impl BibleReference {
+    /// Same reference with verses cited in part taken as whole verses
+    pub fn to_whole_verses(&self) -> BibleReference {
+        let mut reference = self.clone();
+        for location in &mut reference.locations {
+            location.parts.clear();
+        }
+        reference
+    }
+}
 
 // Gen 1:1, 2
 // 3 King 1:3-4
 // II Ki. 3:12-14, 25
-static BIBLE_REFERENCE_PATTERN: &'static str = "(?P<Book>(([1234]|I{1,4})[\\t\\f\\pZ]*)?\\pL+\\.?)[\\t\\f\\pZ]+\
-                                                (?P<Locations>(\
-                                                (?P<Chapter>1?[0-9]?[0-9])\
-                                                (-(?P<ChapterEnd>\\d+)|,\\s*(?P<ChapterNext>\\d+))*\
-                                                (:\\s*(?P<Verse>\\d+))?\
-                                                (-(?P<VerseEnd>\\d+)|,\\s*(?P<VerseNext>\\d+))*\
-                                                \\s?)+)";
+// Ин 3:36—4:2
+// John3:16, 1Kgs.3:4 (known books only)
+// Spelled-out and dotted prefixes and Roman suffixes are joined by `book_start`:
+// First John 3:16, 1st Cor 13, Второе Петра 1:5, Samuel II 7:12, 5. Mose 6:4
+static BOOK_PATTERN: &str =
+    "\\b(?P<Book>(([1-5]|I{1,3}|IV)[\\t\\f\\pZ]*)?\\pL+(['’]\\pL+)*\\.?)[\\t\\f\\pZ]*";
 
-/// Parses string into references
-pub fn parse(string: &str) -> Vec<BibleReference> {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(BIBLE_REFERENCE_PATTERN).unwrap();
+// `{verse}` and `{list}` are replaced with separators of the notation,
+// `{dash}` with range dashes, `{open}` with open range ends
+static LOCATIONS_PATTERN: &str = "(?P<Locations>(\
+                                  (?P<Chapter>[1１]?[0-9０-９]?[0-9０-９])\
+                                  ([\\t\\f\\pZ]*{dash}[\\t\\f\\pZ]*(?P<ChapterEnd>\\d+)|{list}\\s*(?P<ChapterNext>\\d+))*\
+                                  ({verse}\\s*(?P<Verse>\\d+)[a-e]?{open})?\
+                                  ([\\t\\f\\pZ]*{dash}[\\t\\f\\pZ]*(?P<VerseEnd>\\d+)[a-e]?({verse}(?P<VerseEndVerse>\\d+)[a-e]?)?|{list}\\s*(?P<VerseNext>\\d+)[a-e]?({verse}(?P<VerseNextVerse>\\d+)[a-e]?)?{open})*\
+                                  \\s?)+)";
+
+// Rom 3:23; 6:23; 10:9
+static CARRIED_LOCATIONS_PREFIX: &str = "^[\\t\\f\\pZ]*[;；][\\t\\f\\pZ]*";
+
+// Song of Songs
+// Wisdom of Solomon
+// Cantar de los Cantares
+static BOOK_NAME_CONNECTORS: &[&str] = &[
+    "of", "the", "de", "del", "des", "du", "la", "le", "los", "las", "der", "von", "над",
+];
+
+/// Longest number of words joined to the last word of a book name
+const BOOK_NAME_MAX_WORDS: usize = 4;
+
+// John 3:16
+// 1 Cor 13:4-7
+// Song of Songs 2:1, 5
+// John3:16
+static SINGLE_REFERENCE_PATTERN: &str = "^(?P<Book>.*?[\\pL.])[\\t\\f\\pZ]*(?P<Locations>\\d.*)$";
+
+/// Options of `parse_with`
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct ParseOptions {
+    /// Attach location groups following `;` without a book of their own to
+    /// the previous reference: `Rom 3:23; 6:23; 10:9`. On by default
+    pub carry_book: bool,
+    /// Chapter, verse and list separators, `Notation::standard()` by default
+    pub notation: Notation,
+    /// Read known book names without chapters as whole books (`Philemon`,
+    /// `Gen–Deut`), and ranges running from one book into another
+    /// (`Matt 27–Mark 2`). Off by default, since many book names are
+    /// ordinary words too (`Job`, `Acts`, `Numbers`)
+    pub whole_books: bool,
+    /// Read numbers written without verses after a book with one chapter
+    /// as verses: `Jude 5` is `Jude 1:5`. On by default; citation styles
+    /// that always write the chapter may turn it off
+    pub single_chapter_verses: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> ParseOptions {
+        ParseOptions {
+            carry_book: true,
+            notation: Notation::default(),
+            whole_books: false,
+            single_chapter_verses: true,
+        }
+    }
+}
+
+/// Patterns of references and of locations carried over to the previous book
+struct Patterns {
+    reference: Regex,
+    carried: Regex,
+    notation: Notation,
+}
+
+impl Patterns {
+    fn new(notation: &Notation) -> Patterns {
+        let locations = LOCATIONS_PATTERN
+            .replace("{verse}", &notation.verse_pattern())
+            .replace("{list}", &notation.list_pattern())
+            .replace("{open}", &open_end::pattern())
+            .replace("{dash}", &typography::dash_pattern());
+        Patterns {
+            reference: Regex::new(&format!("{}{}", BOOK_PATTERN, locations)).unwrap(),
+            carried: Regex::new(&format!("{}{}", CARRIED_LOCATIONS_PREFIX, locations)).unwrap(),
+            notation: *notation,
+        }
     }
 
-    RE.captures_iter(string)
-        .flat_map(|matches| {
-            if let (Some(book), Some(locations)) = (matches.name("Book"), matches.name("Locations"))
+    /// Patterns of the notation, compiled once for every notation in use
+    fn of(notation: &Notation) -> Arc<Patterns> {
+        lazy_static! {
+            static ref PATTERNS: Mutex<HashMap<Notation, Arc<Patterns>>> =
+                Mutex::new(HashMap::new());
+        }
+        let mut patterns = PATTERNS.lock().unwrap_or_else(PoisonError::into_inner);
+        patterns
+            .entry(*notation)
+            .or_insert_with(|| Arc::new(Patterns::new(notation)))
+            .clone()
+    }
+
+    /// Appends location groups following `;` between the end of the reference
+    /// and `end` of the text
+    fn carry_book(&self, reference: &mut BibleReference, string: &str, end: usize) {
+        let mut position = reference.span.end;
+        while position < end {
+            let locations = match self
+                .carried
+                .captures(&string[position..end])
+                .and_then(|m| m.name("Locations"))
             {
-                Some(BibleReference {
-                    book: book.as_str().to_string(),
-                    locations: parse_locations(locations.as_str()),
-                })
-            } else {
-                None
+                Some(locations) => locations,
+                None => break,
+            };
+            self.append_locations(reference, locations.as_str(), position + locations.start());
+            position += locations.end();
+        }
+    }
+
+    /// Parses locations found at `offset` of the text and appends them to the reference
+    fn append_locations(&self, reference: &mut BibleReference, text: &str, offset: usize) {
+        let (parsed, segments) = parse_locations(text, &self.notation);
+        for (location, span) in parsed {
+            reference.locations.push(location);
+            reference.location_spans.push(span.shift(offset));
+        }
+        reference.segments.extend(segments);
+        if let Some(span) = reference.location_spans.last() {
+            reference.span.end = span.end;
+        }
+    }
+}
+
+/// Parses string into references
+pub fn parse(string: &str) -> Vec<BibleReference> {
+    parse_with(string, &ParseOptions::default())
+}
+
+/// Parses string into references using the given options
+pub fn parse_with(string: &str, options: &ParseOptions) -> Vec<BibleReference> {
+    let patterns = Patterns::of(&options.notation);
+
+    let mut references: Vec<BibleReference> = Vec::new();
+    let mut last_end = 0;
+    let mut position = 0;
+    while let Some(matches) = patterns.reference.captures_at(string, position) {
+        let (book, locations) = match (matches.name("Book"), matches.name("Locations")) {
+            (Some(book), Some(locations)) => (book, locations),
+            _ => break,
+        };
+        let previous_end = last_end;
+        last_end = matches.get(0).map_or(last_end, |m| m.end());
+        position = last_end;
+
+        // "and 1Kgs.3:4": the number belongs to the next book, not to "and"
+        if starts_with_numbered_book(&string[locations.start()..]) {
+            last_end = previous_end;
+            position = locations.start();
+            continue;
+        }
+        // "3:16, 1st Cor 13": the last number starts the next book
+        let locations_end = locations_end(string, locations.start(), locations.end());
+        if locations_end < locations.end() {
+            last_end = locations_end;
+            position = locations_end;
+        }
+        let text = &string[previous_end..book.end()];
+        let start = previous_end + book_start(text, book.start() - previous_end);
+        let name = &string[start..book.end()];
+        // Only known books may be written together with the chapter,
+        // otherwise any word with digits ("COVID19") would be a reference
+        if book.end() == locations.start() && book_in_text(name).is_none() {
+            continue;
+        }
+        if options.carry_book {
+            if let Some(previous) = references.last_mut() {
+                patterns.carry_book(previous, string, start);
             }
-        }).collect()
+        }
+
+        let mut reference = BibleReference {
+            book: name.to_string(),
+            book_id: book_in_text(name),
+            locations: vec![],
+            segments: vec![],
+            span: Span {
+                start,
+                end: book.end(),
+            },
+            book_span: Span {
+                start,
+                end: book.end(),
+            },
+            location_spans: vec![],
+            inferred: false,
+            typography: Typography::default(),
+            book_end: None,
+        };
+        patterns.append_locations(
+            &mut reference,
+            &string[locations.start()..locations_end],
+            locations.start(),
+        );
+        references.push(reference);
+    }
+
+    if options.carry_book {
+        if let Some(previous) = references.last_mut() {
+            patterns.carry_book(previous, string, string.len());
+        }
+    }
+    // "Gen 300": with every location skipped the book alone would be read
+    // as the whole book
+    references.retain(|reference| !reference.locations.is_empty());
+    if options.single_chapter_verses {
+        for reference in &mut references {
+            single_chapter::read_as_verses(reference);
+        }
+    }
+    if options.whole_books {
+        book_range::find_whole_books(string, &mut references);
+        book_range::join_book_ranges(string, &mut references);
+    }
+    for reference in &mut references {
+        reference.typography = Typography::of(reference.span.slice(string));
+    }
+    references
 }
 
-/// Parses string into locations
-fn parse_locations(string: &str) -> Vec<VerseLocation> {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(VERSES_LOCATION_PATTERN).unwrap();
+/// Whether the text starts with a numbered book written together with its
+/// number (`1Kgs`), with an ordinal (`1st Cor`), or with a lone digit and a
+/// dot or a space (`1. Mose`, `1 Cor`), rather than with a chapter
+fn starts_with_numbered_book(text: &str) -> bool {
+    if !text.starts_with(|c| ('1'..='5').contains(&c)) {
+        return false;
+    }
+    let word_end = |start: usize| {
+        text[start..]
+            .char_indices()
+            .find(|&(_, c)| !c.is_alphabetic())
+            .map_or(text.len(), |(index, _)| start + index)
+    };
+    let end = word_end(1);
+    if end > 1 {
+        return Book::from_name(&text[..end]).is_some() || names::ordinal(&text[..end]).is_some();
     }
 
-    RE.captures_iter(string)
-        .flat_map(|matches| {
-            let chapter = match matches.name("Chapter") {
-                Some(group) => match group.as_str().parse().ok() {
-                    Some(chapter) => chapter,
-                    None => return None,
-                },
-                None => return None,
-            };
+    let is_space = |c: char| c == '\t' || c == '\x0c' || (c.is_whitespace() && !c.is_control());
+    let dot = if text[1..].starts_with('.') { 2 } else { 1 };
+    let start = text.len() - text[dot..].trim_start_matches(is_space).len();
+    let end = word_end(start);
+    start > 1 && end > start && Book::from_name(&text[..end]).is_some()
+}
 
-            let chapter_end = match matches.name("ChapterEnd") {
-                Some(group) => group.as_str().parse().ok(),
-                None => None,
-            };
-            let chapter_next = match matches.name("ChapterNext") {
-                Some(group) => group.as_str().parse().ok(),
-                None => None,
-            };
+/// Fewest letters of a name read as a book in free text when it is not
+/// capitalised
+const SHORT_NAME_LETTERS: usize = 3;
 
-            let chapters_range = match (chapter, chapter_next, chapter_end) {
-                (ch, None, None) => vec![ch],
-                (ch, Some(next), None) => vec![ch, next],
-                (ch, None, Some(end)) => (ch..=end).collect(),
-                (ch, Some(next), Some(end)) => {
-                    let mut vec: Vec<u8> = (ch..=end).collect();
-                    vec.push(next);
-                    vec
-                }
-            };
+/// Book of a name found in free text. Short names without a number count
+/// only when capitalised as in the tables, so that words like `is`, `am`
+/// and `ex` are not read as Isaiah, Amos and Exodus
+fn book_in_text(name: &str) -> Option<Book> {
+    let mut letters = name.chars().filter(|c| c.is_alphabetic());
+    let capital = letters.next().is_some_and(char::is_uppercase);
+    let numbered = name.starts_with(|c: char| c.is_ascii_digit());
+    if !capital && !numbered && letters.count() + 1 < SHORT_NAME_LETTERS {
+        return None;
+    }
+    Book::from_name(name)
+}
 
-            let verse = match matches.name("Verse") {
-                Some(group) => group.as_str().parse().ok(),
-                None => None,
-            };
-            let verse_next = match matches.name("VerseNext") {
-                Some(group) => group.as_str().parse().ok(),
-                None => None,
-            };
-            let verse_end = match matches.name("VerseEnd") {
-                Some(group) => group.as_str().parse().ok(),
-                None => None,
-            };
+/// End of the locations from `start` to `end`, without the last number
+/// when it starts the next book (`3:16, 1Kgs`, `3:16, 1st Cor`, `3:16, 1 Cor`,
+/// `3,16 und 2. Kor`)
+fn locations_end(string: &str, start: usize, end: usize) -> usize {
+    let text = string[start..end].trim_end();
+    let rest = text.trim_end_matches(|c: char| c.is_ascii_digit());
+    if rest.len() == text.len() || !starts_with_numbered_book(&string[start + rest.len()..]) {
+        return end;
+    }
+    start + rest.trim_end_matches(|c: char| !c.is_ascii_digit()).len()
+}
 
-            let verses_range = match (verse, verse_next, verse_end) {
-                (Some(verse), None, None) => Some(vec![verse]),
-                (Some(verse), Some(next), None) => Some(vec![verse, next]),
-                (Some(verse), None, Some(end)) => Some((verse..=end).collect()),
-                (Some(verse), Some(next), Some(end)) => {
-                    let mut vec: Vec<u8> = (verse..=end).collect();
-                    vec.push(next);
-                    Some(vec)
-                }
-                _ => None,
-            };
+impl FromStr for BibleReference {
+    type Err = ParseError;
+
+    /// Parses exactly one reference with a known book, e.g. `"John 3:16"`.
+    ///
+    /// Unlike `parse`, nothing is skipped: out of range numbers, descending
+    /// ranges and any text around the reference are reported as errors.
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(SINGLE_REFERENCE_PATTERN).unwrap();
+        }
+
+        let text = string.trim();
+        if text.is_empty() {
+            return Err(ParseError::Empty);
+        }
+        let offset = string.len() - string.trim_start().len();
+
+        let matches = match RE.captures(text) {
+            Some(matches) => matches,
+            None if Book::from_name(text).is_some() => return Err(ParseError::MissingLocation),
+            None => {
+                let book = text
+                    .split(|c: char| c.is_ascii_digit())
+                    .next()
+                    .unwrap_or("");
+                return Err(ParseError::UnknownBook(book.trim().to_string()));
+            }
+        };
+        let (book, locations) = match (matches.name("Book"), matches.name("Locations")) {
+            (Some(book), Some(locations)) => (book, locations),
+            _ => return Err(ParseError::MissingLocation),
+        };
+
+        let book_id = match Book::from_name(book.as_str()) {
+            Some(book_id) => book_id,
+            None => return Err(ParseError::UnknownBook(book.as_str().to_string())),
+        };
+        let location_offset = offset + locations.start();
+        let (locations, segments) =
+            parse_locations_strict(locations.as_str(), &Notation::standard())
+                .map_err(|error| error.shift(location_offset))?;
+        let (locations, location_spans) = locations
+            .into_iter()
+            .map(|(location, span)| (location, span.shift(location_offset)))
+            .unzip();
+
+        let mut reference = BibleReference {
+            book: book.as_str().to_string(),
+            book_id: Some(book_id),
+            locations,
+            segments,
+            span: Span {
+                start: offset,
+                end: offset + text.len(),
+            },
+            book_span: Span {
+                start: offset,
+                end: offset + book.end(),
+            },
+            location_spans,
+            inferred: false,
+            typography: Typography::of(text),
+            book_end: None,
+        };
+        single_chapter::read_as_verses(&mut reference);
+        Ok(reference)
+    }
+}
+
+/// Finds where a book name ending the `text` starts.
+///
+/// The pattern matches only the last word of the name, so preceding words
+/// on the same line are joined to it when together they form a known name
+/// ("Song of Solomon", "1 Кн. Царств"), or, for unknown names, when they
+/// are linked by connector words ("Wisdom of Solomon").
+fn book_start(text: &str, start: usize) -> usize {
+    let words = preceding_words(&text[..start]);
+
+    if let Some(&(offset, _)) = words
+        .iter()
+        .rev()
+        .find(|&&(offset, _)| Book::from_name(&text[offset..]).is_some())
+    {
+        return offset;
+    }
+
+    if Book::from_name(&text[start..]).is_some() {
+        return start;
+    }
+
+    let mut result = start;
+    let mut index = 0;
+    loop {
+        let connectors = words[index..]
+            .iter()
+            .take_while(|&&(_, word)| BOOK_NAME_CONNECTORS.contains(&word.to_lowercase().as_str()))
+            .count();
+        match words.get(index + connectors) {
+            Some(&(offset, word))
+                if connectors > 0 && word.chars().next().is_some_and(char::is_uppercase) =>
+            {
+                result = offset;
+                index += connectors + 1;
+            }
+            _ => return result,
+        }
+    }
+}
+
+/// Words right before the end of `text` on the same line, nearest first
+fn preceding_words(text: &str) -> Vec<(usize, &str)> {
+    let is_space = |c: char| c == '\t' || c == '\x0c' || (c.is_whitespace() && !c.is_control());
+    let is_word = |c: char| c.is_alphanumeric() || c == '.' || c == '\'' || c == '’';
 
-            Some(VerseLocation {
-                chapters: chapters_range,
-                verses: verses_range,
-            })
-        }).collect()
+    let mut words = Vec::new();
+    let mut rest = text;
+    while words.len() < BOOK_NAME_MAX_WORDS {
+        let trimmed = rest.trim_end_matches(is_space);
+        if trimmed.len() == rest.len() {
+            break;
+        }
+        let offset = trimmed
+            .char_indices()
+            .rev()
+            .find(|&(_, c)| !is_word(c))
+            .map_or(0, |(index, c)| index + c.len_utf8());
+        if offset == trimmed.len() {
+            break;
+        }
+        words.push((offset, &trimmed[offset..]));
+        rest = &trimmed[..offset];
+    }
+    words
 }
 
 #[cfg(test)]
@@ -162,6 +618,9 @@ mod tests {
         let v = VerseLocation {
             chapters: vec![1],
             verses: Some(vec![1, 2]),
+            range: None,
+            parts: vec![],
+            open_ranges: vec![],
         };
         assert_eq!(v.chapters, vec![1]);
         assert_eq!(v.verses, Some(vec![1, 2]));
@@ -176,6 +635,9 @@ mod tests {
         let v = VerseLocation {
             chapters: vec![1, 3],
             verses: None,
+            range: None,
+            parts: vec![],
+            open_ranges: vec![],
         };
         assert_eq!(v.chapters, vec![1, 3]);
         assert_eq!(v.verses, None);
@@ -186,10 +648,21 @@ mod tests {
         let v = VerseLocation {
             chapters: vec![1],
             verses: Some(vec![1, 2]),
+            range: None,
+            parts: vec![],
+            open_ranges: vec![],
         };
         let r = BibleReference {
             book: String::from("Gen"),
+            book_id: Some(Book::Genesis),
+            segments: v.segments(),
             locations: vec![v],
+            span: Span::default(),
+            book_span: Span::default(),
+            location_spans: vec![Span::default()],
+            inferred: false,
+            typography: Typography::default(),
+            book_end: None,
         };
         assert_eq!(r.book, "Gen");
         assert_eq!(r.locations[0].chapters, [1]);
@@ -201,21 +674,160 @@ mod tests {
         assert_eq!(c.locations[0].verses, Some(vec![1, 2]));
     }
 
+    #[cfg(feature = "english")]
     #[test]
     fn test_parse_simple() {
         let refs = parse("1Cor 1:1");
         assert_eq!(refs.len(), 1);
         assert_eq!(refs[0].book, "1Cor");
+        assert_eq!(refs[0].book_id, Some(Book::FirstCorinthians));
         assert_eq!(refs[0].locations[0].chapters, [1]);
         assert_eq!(refs[0].locations[0].verses, Some(vec![1]));
     }
 
+    #[cfg(all(feature = "english", feature = "russian"))]
+    #[test]
+    fn test_parse_ordinals() {
+        let text = "First John 3:16, 1st Cor 13:4; Второе Петра 1:5 and Samuel II 7:12";
+        let refs = parse(text);
+        let books: Vec<Option<Book>> = refs.iter().map(|r| r.book_id).collect();
+        assert_eq!(
+            books,
+            [
+                Some(Book::FirstJohn),
+                Some(Book::FirstCorinthians),
+                Some(Book::SecondPeter),
+                Some(Book::SecondSamuel),
+            ]
+        );
+        assert_eq!(refs[0].locations[0].verses, Some(vec![16]));
+        assert_eq!(refs[1].span.slice(text), "1st Cor 13:4");
+        assert_eq!(refs[3].book_span.slice(text), "Samuel II");
+        assert_eq!(
+            "Samuel II 7:12".parse::<BibleReference>().unwrap().book_id,
+            Some(Book::SecondSamuel)
+        );
+
+        let refs = parse("IIII Царств 2:1");
+        assert_eq!(refs[0].book_id, None);
+    }
+
+    #[cfg(feature = "english")]
+    #[test]
+    fn test_parse_compact() {
+        let text = "#John3:16 and 1Kgs.3:4, Gen1:1-2";
+        let refs = parse(text);
+        assert_eq!(refs.len(), 3);
+        assert_eq!(refs[0].book_span.slice(text), "John");
+        assert_eq!(refs[0].location_spans[0].slice(text), "3:16");
+        assert_eq!(refs[1].book, "1Kgs.");
+        assert_eq!(refs[1].book_id, Some(Book::FirstKings));
+        assert_eq!(refs[1].locations[0].verses, Some(vec![4]));
+        assert_eq!(refs[2].book_id, Some(Book::Genesis));
+        assert_eq!(refs[2].locations[0].verses, Some(vec![1, 2]));
+
+        assert!(parse("COVID19 cases, mp3 files, A4 paper").is_empty());
+        let refs = parse("COVID19 and Rom 8:28");
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].book_id, Some(Book::Romans));
+
+        let r: BibleReference = "John3:16".parse().unwrap();
+        assert_eq!(r.book_id, Some(Book::John));
+        assert_eq!(r.locations[0].verses, Some(vec![16]));
+    }
+
+    #[cfg(feature = "english")]
+    #[test]
+    fn test_parse_numbered_after_locations() {
+        let text = "Gen 3:16, 1 Cor 13:4; John 3:16, 2 Tim 3:16 and Gen 3:16 2 Kings 5";
+        let refs = parse(text);
+        let books: Vec<Option<Book>> = refs.iter().map(|r| r.book_id).collect();
+        assert_eq!(
+            books,
+            [
+                Some(Book::Genesis),
+                Some(Book::FirstCorinthians),
+                Some(Book::John),
+                Some(Book::SecondTimothy),
+                Some(Book::Genesis),
+                Some(Book::SecondKings),
+            ]
+        );
+        assert_eq!(refs[0].span.slice(text), "Gen 3:16");
+        assert_eq!(refs[0].locations[0].verses, Some(vec![16]));
+        assert_eq!(refs[1].span.slice(text), "1 Cor 13:4");
+        assert_eq!(refs[4].span.slice(text), "Gen 3:16");
+        assert_eq!(refs[5].span.slice(text), "2 Kings 5");
+
+        let refs = parse("see 1 Cor 13:4");
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].book, "1 Cor");
+
+        let refs = parse("Gen 1, 2 and Ps 23 1 Notes");
+        assert_eq!(refs.len(), 2);
+        assert_eq!(refs[0].locations[0].chapters, [1, 2]);
+        assert_eq!(refs[1].locations[1].chapters, [1]);
+    }
+
+    #[cfg(feature = "english")]
+    #[test]
+    fn test_parse_short_words() {
+        let refs = parse("He is 3 years old");
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].book, "is");
+        assert_eq!(refs[0].book_id, None);
+
+        let refs = parse("am 5 and ex 3");
+        assert_eq!(refs.len(), 2);
+        assert_eq!(refs[0].book_id, None);
+        assert_eq!(refs[1].book_id, None);
+
+        let refs = parse("Is 53:5, Am 5:24 and Ex 20");
+        let books: Vec<Option<Book>> = refs.iter().map(|r| r.book_id).collect();
+        assert_eq!(books, [Some(Book::Isaiah), Some(Book::Amos), Some(Book::Exodus)]);
+        assert_eq!(parse("gen 1:1")[0].book_id, Some(Book::Genesis));
+        assert_eq!(parse("1jn 3:16")[0].book_id, Some(Book::FirstJohn));
+    }
+
+    #[cfg(feature = "german")]
+    #[test]
+    fn test_parse_dotted_ordinals() {
+        let options = ParseOptions {
+            notation: Notation::german(),
+            ..ParseOptions::default()
+        };
+        let text = "Lies 1. Mose 1,1 und 5. Mose 6,4";
+        let refs = parse_with(text, &options);
+        assert_eq!(refs.len(), 2);
+        assert_eq!(refs[0].book_id, Some(Book::Genesis));
+        assert_eq!(refs[0].span.slice(text), "1. Mose 1,1");
+        assert_eq!(refs[0].locations[0].verses, Some(vec![1]));
+        assert_eq!(refs[1].book_id, Some(Book::Deuteronomy));
+        assert_eq!(refs[1].span.slice(text), "5. Mose 6,4");
+
+        let text = "Siehe 1. Kor 13,4 und 2. Kor 5,17";
+        let refs = parse_with(text, &options);
+        assert_eq!(refs.len(), 2);
+        assert_eq!(refs[0].book_id, Some(Book::FirstCorinthians));
+        assert_eq!(refs[0].span.slice(text), "1. Kor 13,4");
+        assert_eq!(refs[1].book_id, Some(Book::SecondCorinthians));
+        assert_eq!(refs[1].span.slice(text), "2. Kor 5,17");
+        assert_eq!(refs[1].locations[0].verses, Some(vec![17]));
+
+        let refs = parse("Röm 8:28, 1. Kor 13:4");
+        assert_eq!(refs.len(), 2);
+        assert_eq!(refs[0].locations[0].verses, Some(vec![28]));
+        assert_eq!(refs[1].book_id, Some(Book::FirstCorinthians));
+    }
+
+    #[cfg(feature = "english")]
     #[test]
     fn test_parse_singleline() {
         let refs = parse("II Ki. 3:12-14, 25");
 
         assert_eq!(refs.len(), 1);
         assert_eq!(refs[0].book, "II Ki.");
+        assert_eq!(refs[0].book_id, Some(Book::SecondKings));
         assert_eq!(refs[0].locations[0].chapters, [3]);
         assert_eq!(refs[0].locations[0].verses, Some(vec![12, 13, 14, 25]));
     }
@@ -232,6 +844,14 @@ mod tests {
         assert_eq!(refs.len(), 0);
     }
 
+    #[test]
+    fn test_wrong_input_3() {
+        let refs = parse("Gen 300 and Ex 2");
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].book, "Ex");
+    }
+
+    #[cfg(feature = "english")]
     #[test]
     fn test_parse_multiline() {
         let refs = parse(
@@ -263,6 +883,7 @@ mod tests {
         assert_eq!(refs[3].locations[0].verses, None);
 
         assert_eq!(refs[4].book, "Jh");
+        assert_eq!(refs[4].book_id, Some(Book::John));
         assert_eq!(refs[4].locations[0].chapters, [1]);
         assert_eq!(refs[4].locations[0].verses, Some(vec![2, 3, 4, 7]));
 
@@ -272,4 +893,174 @@ mod tests {
         assert_eq!(refs[5].locations[1].chapters, [2]);
         assert_eq!(refs[5].locations[1].verses, Some(vec![2, 5]));
     }
+
+    #[cfg(feature = "english")]
+    #[test]
+    fn test_parse_multiword_book() {
+        let refs = parse("Song of Solomon 2:1 and Acts of the Apostles 2");
+        assert_eq!(refs.len(), 2);
+        assert_eq!(refs[0].book, "Song of Solomon");
+        assert_eq!(refs[0].book_id, Some(Book::SongOfSongs));
+        assert_eq!(refs[0].locations[0].chapters, [2]);
+        assert_eq!(refs[1].book, "Acts of the Apostles");
+        assert_eq!(refs[1].book_id, Some(Book::Acts));
+
+        let refs = parse("in the book of John 3:16");
+        assert_eq!(refs[0].book, "John");
+    }
+
+    #[cfg(all(feature = "russian", feature = "ukrainian"))]
+    #[test]
+    fn test_parse_multiword_book_cyrillic() {
+        let refs = parse("Wisdom of Solomon 1:1; 1 Кн. Царств 3:4; Пісня над піснями 2");
+        assert_eq!(refs.len(), 3);
+        assert_eq!(refs[0].book, "Wisdom of Solomon");
+        assert_eq!(refs[0].book_id, None);
+        assert_eq!(refs[1].book, "1 Кн. Царств");
+        assert_eq!(refs[1].book_id, Some(Book::FirstSamuel));
+        assert_eq!(refs[2].book, "Пісня над піснями");
+        assert_eq!(refs[2].book_id, Some(Book::SongOfSongs));
+    }
+
+    #[test]
+    fn test_parse_cross_chapter_range() {
+        let refs = parse("Gen 1:30-2:3; Ин 3:36—4:2, 5");
+        assert_eq!(refs.len(), 2);
+
+        let range = refs[0].locations[0].range.unwrap();
+        assert_eq!(
+            range.start,
+            VersePoint {
+                chapter: 1,
+                verse: 30
+            }
+        );
+        assert_eq!(
+            range.end,
+            VersePoint {
+                chapter: 2,
+                verse: 3
+            }
+        );
+        assert_eq!(refs[0].locations[0].chapters, [1, 2]);
+        assert_eq!(refs[0].locations[0].verses, None);
+
+        let range = refs[1].locations[0].range.unwrap();
+        assert_eq!(
+            range.start,
+            VersePoint {
+                chapter: 3,
+                verse: 36
+            }
+        );
+        assert_eq!(
+            range.end,
+            VersePoint {
+                chapter: 4,
+                verse: 2
+            }
+        );
+        assert_eq!(refs[1].locations[1].chapters, [4]);
+        assert_eq!(refs[1].locations[1].verses, Some(vec![5]));
+        assert_eq!(refs[1].locations[1].range, None);
+    }
+
+    #[test]
+    fn test_parse_spans() {
+        let text = "See Song of Songs 2:1-3, 5 4:1; and Быт 1";
+        let refs = parse(text);
+        assert_eq!(refs.len(), 2);
+
+        assert_eq!(refs[0].span.slice(text), "Song of Songs 2:1-3, 5 4:1");
+        assert_eq!(refs[0].book_span.slice(text), "Song of Songs");
+        assert_eq!(refs[0].location_spans.len(), 2);
+        assert_eq!(refs[0].location_spans[0].slice(text), "2:1-3, 5");
+        assert_eq!(refs[0].location_spans[1].slice(text), "4:1");
+
+        assert_eq!(refs[1].span.slice(text), "Быт 1");
+        assert_eq!(refs[1].span.positions(text).0.column, 37);
+    }
+
+    #[cfg(feature = "english")]
+    #[test]
+    fn test_parse_carried_book() {
+        let text = "Rom 3:23; 6:23; 10:9 and Gen 1:1; 3:5; 12";
+        let refs = parse(text);
+        assert_eq!(refs.len(), 2);
+
+        assert_eq!(refs[0].book_id, Some(Book::Romans));
+        assert_eq!(refs[0].locations.len(), 3);
+        assert_eq!(refs[0].locations[2].chapters, [10]);
+        assert_eq!(refs[0].locations[2].verses, Some(vec![9]));
+        assert_eq!(refs[0].span.slice(text), "Rom 3:23; 6:23; 10:9");
+        assert_eq!(refs[0].location_spans[1].slice(text), "6:23");
+
+        assert_eq!(refs[1].locations.len(), 3);
+        assert_eq!(refs[1].locations[2].chapters, [12]);
+        assert_eq!(refs[1].locations[2].verses, None);
+        assert_eq!(refs[1].segments.len(), 3);
+
+        let refs = parse("Gen 1; 2 Cor 3:4");
+        assert_eq!(refs.len(), 2);
+        assert_eq!(refs[0].locations.len(), 1);
+        assert_eq!(refs[1].book_id, Some(Book::SecondCorinthians));
+
+        let options = ParseOptions {
+            carry_book: false,
+            ..ParseOptions::default()
+        };
+        let refs = parse_with("Rom 3:23; 6:23", &options);
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].locations.len(), 1);
+        assert_eq!(refs[0].span, Span { start: 0, end: 8 });
+    }
+
+    #[cfg(feature = "english")]
+    #[test]
+    fn test_from_str() {
+        let r: BibleReference = "John 3:16".parse().unwrap();
+        assert_eq!(r.book, "John");
+        assert_eq!(r.book_id, Some(Book::John));
+        assert_eq!(r.locations[0].chapters, [3]);
+        assert_eq!(r.locations[0].verses, Some(vec![16]));
+
+        let r: BibleReference = " Song of Songs 2:1, 5 ".parse().unwrap();
+        assert_eq!(r.book_id, Some(Book::SongOfSongs));
+        assert_eq!(r.span, Span { start: 1, end: 21 });
+        assert_eq!(r.location_spans, [Span { start: 15, end: 21 }]);
+    }
+
+    #[cfg(feature = "english")]
+    #[test]
+    fn test_from_str_errors() {
+        assert_eq!("".parse::<BibleReference>().err(), Some(ParseError::Empty));
+        assert_eq!(
+            "Foo 1:1".parse::<BibleReference>().err(),
+            Some(ParseError::UnknownBook("Foo".to_string()))
+        );
+        assert_eq!(
+            "John".parse::<BibleReference>().err(),
+            Some(ParseError::MissingLocation)
+        );
+        assert_eq!(
+            "Gen 1:300".parse::<BibleReference>().err(),
+            Some(ParseError::NumberOutOfRange(Span { start: 6, end: 9 }))
+        );
+        assert_eq!(
+            "Gen 0".parse::<BibleReference>().err(),
+            Some(ParseError::NumberOutOfRange(Span { start: 4, end: 5 }))
+        );
+        assert_eq!(
+            "Gen 1:0".parse::<BibleReference>().err(),
+            Some(ParseError::NumberOutOfRange(Span { start: 6, end: 7 }))
+        );
+        assert_eq!(
+            "Gen 1:5-3".parse::<BibleReference>().err(),
+            Some(ParseError::DescendingRange(Span { start: 4, end: 9 }))
+        );
+        assert_eq!(
+            "Gen 1:5 and more".parse::<BibleReference>().err(),
+            Some(ParseError::UnexpectedText(Span { start: 8, end: 9 }))
+        );
+    }
 }
@@ -0,0 +1,666 @@
+//! Verse locations tokenizer and parser
+//!
+//! Locations string is split into tokens, tokens into whitespace separated
+//! groups, and groups into comma separated items. Items are read strictly
+//! in source order, so lists and ranges of any length can be mixed freely:
+//!
+//! ```text
+//! 1,3,5,7            chapters 1, 3, 5 and 7
+//! 1:1,3-5            chapter 1, verses 1, 3, 4 and 5
+//! 1:1, 2:3-4         chapter 1 verse 1, chapter 2 verses 3 and 4
+//! 1:30-2:3, 5        from 1:30 to 2:3, then chapter 2 verse 5
+//! 1:1b-3a            chapter 1, second part of verse 1 to first part of verse 3
+//! ```
+
+use std::iter::Peekable;
+
+use open_end::open_end;
+use segment::Segment;
+use typography::fold;
+use {
+    Notation, OpenEnd, OpenRange, ParseError, Span, VerseLocation, VersePart, VersePoint,
+    VerseRange,
+};
+
+/// Lexical token of locations string
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Token {
+    /// Chapter or verse number
+    Number(u16),
+    /// Chapter and verse separator
+    Colon,
+    /// List separator
+    Comma,
+    /// Range separator
+    Dash,
+    /// Letter suffix of a verse cited in part: `a` in `2a`
+    Part(char),
+    /// Open end of a range: `ff.` in `28ff.`, `end` in `169-end`
+    Open(OpenEnd),
+    /// Whitespace between location groups
+    Space,
+    /// Anything else
+    Other,
+}
+
+/// Comma separated item of a group
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Item {
+    /// Chapters or verses, depending on context: `5`, `5-8`
+    Span(u8, u8),
+    /// Verses of the chapter: `3:16`, `3:16-18`
+    Verses(u8, u8, u8),
+    /// Verses across chapters: `1:30-2:3`
+    Range(VerseRange),
+}
+
+/// Suffixes of an item: parts of its first and last verse (`1b-3a`), and
+/// open end (`28ff.`)
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+struct Suffixes {
+    first: Option<char>,
+    last: Option<char>,
+    open: Option<OpenEnd>,
+}
+
+/// Locations with their byte spans in the string, and segments they are built from
+pub(crate) type Locations = (Vec<(VerseLocation, Span)>, Vec<Segment>);
+
+/// Parses string into locations, skipping malformed items
+pub(crate) fn parse_locations(string: &str, notation: &Notation) -> Locations {
+    read(string, notation, false).unwrap_or_default()
+}
+
+/// Parses string into locations, failing on the first malformed item
+pub(crate) fn parse_locations_strict(
+    string: &str,
+    notation: &Notation,
+) -> Result<Locations, ParseError> {
+    read(string, notation, true)
+}
+
+fn read(string: &str, notation: &Notation, strict: bool) -> Result<Locations, ParseError> {
+    let mut builder = Builder::default();
+    let all = tokenize(string, notation);
+    let mut tokens = all.iter().cloned().peekable();
+    let eof = Span {
+        start: string.len(),
+        end: string.len(),
+    };
+
+    while tokens.peek().is_some() {
+        let start = tokens.peek().map_or(0, |&(_, span)| span.start);
+        let mut end = start;
+        match parse_item(&mut tokens, &mut end, eof) {
+            Ok((item, suffixes)) => {
+                let span = Span { start, end };
+                if strict && item.is_descending() {
+                    return Err(ParseError::DescendingRange(span));
+                }
+                if strict {
+                    // Chapters and verses are counted from one
+                    let zero = all.iter().find(|&&(token, number)| {
+                        token == Token::Number(0)
+                            && span.start <= number.start
+                            && number.end <= span.end
+                    });
+                    if let Some(&(_, zero)) = zero {
+                        return Err(ParseError::NumberOutOfRange(zero));
+                    }
+                }
+                builder.push(item, suffixes, span)
+            }
+            Err(error) => {
+                if strict {
+                    return Err(error);
+                }
+                skip_item(&mut tokens)
+            }
+        }
+        match tokens.next() {
+            Some((Token::Space, _)) => builder.flush_group(),
+            Some((Token::Comma, _)) | None => {}
+            Some((_, span)) => {
+                if strict {
+                    return Err(ParseError::UnexpectedText(span));
+                }
+                skip_item(&mut tokens)
+            }
+        }
+    }
+
+    Ok(builder.finish())
+}
+
+/// Splits string into tokens, reading separators of the notation. Whitespace
+/// is only kept where it separates location groups (`1:1-2 2:2`), not around
+/// list or verse separators and dashes
+fn tokenize(string: &str, notation: &Notation) -> Vec<(Token, Span)> {
+    let mut tokens: Vec<(Token, Span)> = Vec::new();
+    let mut chars = string.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let mut end = start + c.len_utf8();
+        let token = match fold(c) {
+            '0'..='9' => {
+                let mut number = fold(c).to_digit(10).unwrap_or(0);
+                while let Some(&(index, c)) = chars.peek() {
+                    match fold(c).to_digit(10) {
+                        Some(digit) => number = number.saturating_mul(10).saturating_add(digit),
+                        None => break,
+                    }
+                    end = index + c.len_utf8();
+                    chars.next();
+                }
+                Token::Number(number.min(u32::from(u16::MAX)) as u16)
+            }
+            c if notation.is_verse_separator(c) => Token::Colon,
+            c if notation.is_list_separator(c) => Token::Comma,
+            '-' => {
+                if let Some(&(Token::Space, _)) = tokens.last() {
+                    tokens.pop();
+                }
+                Token::Dash
+            }
+            // Suffix written right after the verse number, not starting a word
+            c @ 'a'..='e'
+                if matches!(tokens.last(), Some(&(Token::Number(_), span)) if span.end == start)
+                    && !chars.peek().is_some_and(|&(_, next)| next.is_alphabetic()) =>
+            {
+                Token::Part(c)
+            }
+            c if c.is_whitespace() => match tokens.last() {
+                Some(&(Token::Comma, _))
+                | Some(&(Token::Colon, _))
+                | Some(&(Token::Dash, _))
+                | Some(&(Token::Space, _))
+                | None => continue,
+                _ => Token::Space,
+            },
+            // Verse word (`3 v 16`) or open end (`28 ff.`) with an optional
+            // dot, taking the place of the space before it
+            c if c.is_alphabetic() => {
+                let word_end = word_end(string, start);
+                let word = &string[start..word_end];
+                let token = match open_end(word) {
+                    Some(open) => Token::Open(open),
+                    None if notation.is_verse_word(word) => Token::Colon,
+                    None => Token::Other,
+                };
+                if token != Token::Other {
+                    while chars.peek().is_some_and(|&(index, _)| index < word_end) {
+                        chars.next();
+                    }
+                    end = word_end;
+                    if let Some(&(Token::Space, _)) = tokens.last() {
+                        tokens.pop();
+                    }
+                }
+                token
+            }
+            _ => Token::Other,
+        };
+        tokens.push((token, Span { start, end }));
+    }
+
+    tokens
+}
+
+/// End of the word starting at `start`, with a trailing dot
+fn word_end(string: &str, start: usize) -> usize {
+    let rest = &string[start..];
+    let letters = rest
+        .char_indices()
+        .find(|&(_, c)| !c.is_alphabetic())
+        .map_or(rest.len(), |(index, _)| index);
+    let dot = if rest[letters..].starts_with('.') {
+        1
+    } else {
+        0
+    };
+    start + letters + dot
+}
+
+/// Reads `N`, `N-M`, `N:V`, `N:V-W`, `N:V-M:W` or `N-M:W`, verses
+/// optionally with part suffixes, and `N:V` with an open end (`N:Vff.`,
+/// `N:V-end`), moving `end` past the last token read
+fn parse_item<I>(
+    tokens: &mut Peekable<I>,
+    end: &mut usize,
+    eof: Span,
+) -> Result<(Item, Suffixes), ParseError>
+where
+    I: Iterator<Item = (Token, Span)>,
+{
+    let mut suffixes = Suffixes::default();
+    let first = number(tokens, end, eof)?;
+    suffixes.first = part(tokens, end);
+    let verse = if peek(tokens) == Some(Token::Colon) {
+        tokens.next();
+        let verse = number(tokens, end, eof)?;
+        suffixes.first = part(tokens, end);
+        Some(verse)
+    } else {
+        None
+    };
+    let single = match verse {
+        Some(verse) => Item::Verses(first, verse, verse),
+        None => Item::Span(first, first),
+    };
+
+    match peek(tokens) {
+        Some(Token::Dash) => {
+            tokens.next();
+        }
+        Some(Token::Open(open)) => {
+            suffixes.open = Some(open);
+            end_with(tokens, end);
+            return Ok((single, suffixes));
+        }
+        _ => return Ok((single, suffixes)),
+    }
+    if let Some(Token::Open(open)) = peek(tokens) {
+        suffixes.open = Some(open);
+        end_with(tokens, end);
+        return Ok((single, suffixes));
+    }
+
+    let last = number(tokens, end, eof)?;
+    suffixes.last = part(tokens, end);
+    let last_verse = if peek(tokens) == Some(Token::Colon) {
+        tokens.next();
+        let verse = number(tokens, end, eof)?;
+        suffixes.last = part(tokens, end);
+        Some(verse)
+    } else {
+        None
+    };
+
+    let item = match (verse, last_verse) {
+        (None, None) => Item::Span(first, last),
+        (Some(verse), None) => Item::Verses(first, verse, last),
+        (verse, Some(last_verse)) => Item::Range(VerseRange {
+            start: VersePoint {
+                chapter: first,
+                verse: verse.unwrap_or(1),
+            },
+            end: VersePoint {
+                chapter: last,
+                verse: last_verse,
+            },
+        }),
+    };
+    Ok((item, suffixes))
+}
+
+/// Takes the next token as the last one of the item
+fn end_with<I: Iterator<Item = (Token, Span)>>(tokens: &mut Peekable<I>, end: &mut usize) {
+    if let Some((_, span)) = tokens.next() {
+        *end = span.end;
+    }
+}
+
+/// Reads part suffix of the verse just read
+fn part<I: Iterator<Item = (Token, Span)>>(
+    tokens: &mut Peekable<I>,
+    end: &mut usize,
+) -> Option<char> {
+    match tokens.peek() {
+        Some(&(Token::Part(part), span)) => {
+            tokens.next();
+            *end = span.end;
+            Some(part)
+        }
+        _ => None,
+    }
+}
+
+/// Kind of the next token
+fn peek<I: Iterator<Item = (Token, Span)>>(tokens: &mut Peekable<I>) -> Option<Token> {
+    tokens.peek().map(|&(token, _)| token)
+}
+
+/// Reads number fitting into `u8`
+fn number<I>(tokens: &mut Peekable<I>, end: &mut usize, eof: Span) -> Result<u8, ParseError>
+where
+    I: Iterator<Item = (Token, Span)>,
+{
+    match tokens.peek() {
+        Some(&(Token::Number(number), span)) => {
+            tokens.next();
+            *end = span.end;
+            if number <= u16::from(u8::MAX) {
+                Ok(number as u8)
+            } else {
+                Err(ParseError::NumberOutOfRange(span))
+            }
+        }
+        Some(&(_, span)) => Err(ParseError::UnexpectedText(span)),
+        None => Err(ParseError::UnexpectedText(eof)),
+    }
+}
+
+/// Drops tokens of malformed item up to the next separator
+fn skip_item<I: Iterator<Item = (Token, Span)>>(tokens: &mut Peekable<I>) {
+    while let Some(token) = peek(tokens) {
+        if token == Token::Comma || token == Token::Space {
+            break;
+        }
+        tokens.next();
+    }
+}
+
+impl Item {
+    fn is_descending(&self) -> bool {
+        match *self {
+            Item::Span(start, end) | Item::Verses(_, start, end) => start > end,
+            Item::Range(range) => range.start > range.end,
+        }
+    }
+}
+
+/// Expands `start..=end`, or just `start` when the range is descending
+fn expand(start: u8, end: u8) -> Vec<u8> {
+    if start <= end {
+        (start..=end).collect()
+    } else {
+        vec![start]
+    }
+}
+
+/// Parts of the first and the last verse, the last one only when the
+/// range is not descending
+fn verse_parts(start: VersePoint, end: VersePoint, suffixes: Suffixes) -> Vec<VersePart> {
+    let first = suffixes.first.map(|part| VersePart { point: start, part });
+    let last = suffixes
+        .last
+        .filter(|_| start < end)
+        .map(|part| VersePart { point: end, part });
+    first.into_iter().chain(last).collect()
+}
+
+/// Collects items into locations
+#[derive(Default)]
+struct Builder {
+    locations: Vec<(VerseLocation, Span)>,
+    segments: Vec<Segment>,
+    current: Option<(VerseLocation, Span)>,
+    /// Chapter of the verses being listed, `None` while listing chapters
+    chapter: Option<u8>,
+}
+
+impl Builder {
+    fn push(&mut self, item: Item, suffixes: Suffixes, span: Span) {
+        let open_range = |chapter, verse| {
+            suffixes.open.map(|end| OpenRange {
+                start: VersePoint { chapter, verse },
+                end,
+            })
+        };
+        match (item, self.chapter) {
+            (Item::Span(start, end), chapter) => {
+                self.segments.push(match chapter {
+                    Some(chapter) => Segment::verses(
+                        VersePoint {
+                            chapter,
+                            verse: start,
+                        },
+                        VersePoint {
+                            chapter,
+                            verse: end,
+                        },
+                    ),
+                    None => Segment::chapters(start, end),
+                });
+                let location = &mut self.current.get_or_insert_with(|| {
+                    let location = VerseLocation {
+                        chapters: chapter.into_iter().collect(),
+                        verses: chapter.map(|_| vec![]),
+                        range: None,
+                        parts: vec![],
+                        open_ranges: vec![],
+                    };
+                    (location, span)
+                });
+                location.1.end = span.end;
+                match (chapter, &mut location.0.verses) {
+                    (Some(chapter), &mut Some(ref mut verses)) => {
+                        verses.extend(expand(start, end));
+                        location.0.parts.extend(verse_parts(
+                            VersePoint {
+                                chapter,
+                                verse: start,
+                            },
+                            VersePoint {
+                                chapter,
+                                verse: end,
+                            },
+                            suffixes,
+                        ));
+                        location.0.open_ranges.extend(open_range(chapter, start));
+                    }
+                    _ => location.0.chapters.extend(expand(start, end)),
+                }
+            }
+            (Item::Verses(chapter, start, end), _) => {
+                self.segments.push(Segment::verses(
+                    VersePoint {
+                        chapter,
+                        verse: start,
+                    },
+                    VersePoint {
+                        chapter,
+                        verse: end,
+                    },
+                ));
+                self.flush();
+                self.chapter = Some(chapter);
+                let location = VerseLocation {
+                    chapters: vec![chapter],
+                    verses: Some(expand(start, end)),
+                    range: None,
+                    parts: verse_parts(
+                        VersePoint {
+                            chapter,
+                            verse: start,
+                        },
+                        VersePoint {
+                            chapter,
+                            verse: end,
+                        },
+                        suffixes,
+                    ),
+                    open_ranges: open_range(chapter, start).into_iter().collect(),
+                };
+                self.current = Some((location, span));
+            }
+            (Item::Range(range), _) => {
+                self.segments.push(Segment::verses(range.start, range.end));
+                self.flush();
+                self.chapter = Some(range.end.chapter);
+                let location = VerseLocation {
+                    chapters: expand(range.start.chapter, range.end.chapter),
+                    verses: None,
+                    range: Some(range),
+                    parts: verse_parts(range.start, range.end, suffixes),
+                    open_ranges: vec![],
+                };
+                self.locations.push((location, span));
+            }
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Some(location) = self.current.take() {
+            self.locations.push(location);
+        }
+    }
+
+    fn flush_group(&mut self) {
+        self.flush();
+        self.chapter = None;
+    }
+
+    fn finish(mut self) -> Locations {
+        self.flush();
+        (self.locations, self.segments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn locations(string: &str) -> Vec<VerseLocation> {
+        parse_locations(string, &Notation::standard())
+            .0
+            .into_iter()
+            .map(|(location, _)| location)
+            .collect()
+    }
+
+    fn location(chapters: Vec<u8>, verses: Option<Vec<u8>>) -> VerseLocation {
+        VerseLocation {
+            chapters,
+            verses,
+            range: None,
+            parts: vec![],
+            open_ranges: vec![],
+        }
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("1:2-3, 5 6", &Notation::standard())
+                .into_iter()
+                .map(|(token, _)| token)
+                .collect::<Vec<_>>(),
+            vec![
+                Token::Number(1),
+                Token::Colon,
+                Token::Number(2),
+                Token::Dash,
+                Token::Number(3),
+                Token::Comma,
+                Token::Number(5),
+                Token::Space,
+                Token::Number(6),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_long_lists() {
+        assert_eq!(locations("1,3,5,7"), vec![location(vec![1, 3, 5, 7], None)]);
+        assert_eq!(
+            locations("1:1,3-5"),
+            vec![location(vec![1], Some(vec![1, 3, 4, 5]))]
+        );
+        assert_eq!(
+            locations("2:9,1-3,7,12-13"),
+            vec![location(vec![2], Some(vec![9, 1, 2, 3, 7, 12, 13]))]
+        );
+        assert_eq!(
+            locations("1-2,4,6-7"),
+            vec![location(vec![1, 2, 4, 6, 7], None)]
+        );
+    }
+
+    #[test]
+    fn test_chapter_switch() {
+        assert_eq!(
+            locations("1:1, 2:3-4, 6"),
+            vec![
+                location(vec![1], Some(vec![1])),
+                location(vec![2], Some(vec![3, 4, 6])),
+            ]
+        );
+        assert_eq!(
+            locations("3, 5:1"),
+            vec![location(vec![3], None), location(vec![5], Some(vec![1]))]
+        );
+        assert_eq!(
+            locations("1:1-2 2:2,5"),
+            vec![
+                location(vec![1], Some(vec![1, 2])),
+                location(vec![2], Some(vec![2, 5])),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_malformed_items() {
+        assert_eq!(locations("1:300, 4"), vec![location(vec![4], None)]);
+        assert_eq!(locations("1:5-3"), vec![location(vec![1], Some(vec![5]))]);
+    }
+
+    #[test]
+    fn test_verse_parts() {
+        let part = |chapter, verse, part| VersePart {
+            point: VersePoint { chapter, verse },
+            part,
+        };
+        let parsed = parse_locations("1:2a, 4b-6a 2:30b-3:1", &Notation::standard()).0;
+        assert_eq!(parsed[0].0.verses, Some(vec![2, 4, 5, 6]));
+        assert_eq!(
+            parsed[0].0.parts,
+            [part(1, 2, 'a'), part(1, 4, 'b'), part(1, 6, 'a')]
+        );
+        assert_eq!(parsed[0].1, Span { start: 0, end: 11 });
+        assert_eq!(parsed[1].0.parts, [part(2, 30, 'b')]);
+
+        assert_eq!(locations("1:2"), vec![location(vec![1], Some(vec![2]))]);
+        assert_eq!(
+            parse_locations_strict("1:2ab", &Notation::standard()),
+            Err(ParseError::UnexpectedText(Span { start: 3, end: 4 }))
+        );
+    }
+
+    #[test]
+    fn test_spans() {
+        let spans: Vec<Span> = parse_locations("1:1-2, 4 2:10, 3:1—4:2", &Notation::standard())
+            .0
+            .into_iter()
+            .map(|(_, span)| span)
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                Span { start: 0, end: 8 },
+                Span { start: 9, end: 13 },
+                Span { start: 15, end: 24 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strict() {
+        assert_eq!(
+            parse_locations_strict("3:16", &Notation::standard())
+                .map(|(locations, _)| locations.len()),
+            Ok(1)
+        );
+        assert_eq!(
+            parse_locations_strict("1:300", &Notation::standard()),
+            Err(ParseError::NumberOutOfRange(Span { start: 2, end: 5 }))
+        );
+        assert_eq!(
+            parse_locations_strict("1:2, 0:3", &Notation::standard()),
+            Err(ParseError::NumberOutOfRange(Span { start: 5, end: 6 }))
+        );
+        assert_eq!(
+            parse_locations_strict("1:5-3", &Notation::standard()),
+            Err(ParseError::DescendingRange(Span { start: 0, end: 5 }))
+        );
+        assert_eq!(
+            parse_locations_strict("1:5 foo", &Notation::standard()),
+            Err(ParseError::UnexpectedText(Span { start: 4, end: 5 }))
+        );
+        assert_eq!(
+            parse_locations_strict("1:", &Notation::standard()),
+            Err(ParseError::UnexpectedText(Span { start: 2, end: 2 }))
+        );
+    }
+}
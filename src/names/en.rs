@@ -0,0 +1,91 @@
+//! English book names
+
+use book::Book;
+
+/// Names and common abbreviations, full name first
+pub fn names(book: Book) -> &'static [&'static str] {
+    match book {
+        Book::Genesis => &["Genesis", "Gen", "Ge", "Gn"],
+        Book::Exodus => &["Exodus", "Exod", "Exo", "Ex"],
+        Book::Leviticus => &["Leviticus", "Lev", "Le", "Lv"],
+        Book::Numbers => &["Numbers", "Num", "Nu", "Nm", "Nb"],
+        Book::Deuteronomy => &["Deuteronomy", "Deut", "De", "Dt"],
+        Book::Joshua => &["Joshua", "Josh", "Jos", "Jsh"],
+        Book::Judges => &["Judges", "Judg", "Jdgs", "Jdg", "Jg"],
+        Book::Ruth => &["Ruth", "Rth", "Ru"],
+        Book::FirstSamuel => &["1 Samuel", "1 Sam", "1 Sa", "1 Sm"],
+        Book::SecondSamuel => &["2 Samuel", "2 Sam", "2 Sa", "2 Sm"],
+        Book::FirstKings => &["1 Kings", "1 Kgs", "1 Kin", "1 Ki"],
+        Book::SecondKings => &["2 Kings", "2 Kgs", "2 Kin", "2 Ki"],
+        Book::FirstChronicles => &["1 Chronicles", "1 Chron", "1 Chr", "1 Ch"],
+        Book::SecondChronicles => &["2 Chronicles", "2 Chron", "2 Chr", "2 Ch"],
+        Book::Ezra => &["Ezra", "Ezr"],
+        Book::Nehemiah => &["Nehemiah", "Neh", "Ne"],
+        Book::Esther => &["Esther", "Esth", "Est", "Es"],
+        Book::Job => &["Job", "Jb"],
+        Book::Psalms => &["Psalms", "Psalm", "Psa", "Psm", "Pss", "Ps"],
+        Book::Proverbs => &["Proverbs", "Prov", "Pro", "Prv", "Pr"],
+        Book::Ecclesiastes => &["Ecclesiastes", "Eccles", "Eccl", "Ecc", "Ec", "Qoh"],
+        Book::SongOfSongs => &[
+            "Song of Songs",
+            "Song of Solomon",
+            "Song of Sol",
+            "Canticles",
+            "Cant",
+            "Song",
+            "SOS",
+            "Sg",
+        ],
+        Book::Isaiah => &["Isaiah", "Isa", "Is"],
+        Book::Jeremiah => &["Jeremiah", "Jer", "Je", "Jr"],
+        Book::Lamentations => &["Lamentations", "Lamentations of Jeremiah", "Lam", "La"],
+        Book::Ezekiel => &["Ezekiel", "Ezek", "Eze", "Ezk"],
+        Book::Daniel => &["Daniel", "Dan", "Da", "Dn"],
+        Book::Hosea => &["Hosea", "Hos", "Ho"],
+        Book::Joel => &["Joel", "Jl"],
+        Book::Amos => &["Amos", "Am"],
+        Book::Obadiah => &["Obadiah", "Obad", "Ob"],
+        Book::Jonah => &["Jonah", "Jon", "Jnh"],
+        Book::Micah => &["Micah", "Mic", "Mc"],
+        Book::Nahum => &["Nahum", "Nah", "Na"],
+        Book::Habakkuk => &["Habakkuk", "Hab", "Hb"],
+        Book::Zephaniah => &["Zephaniah", "Zeph", "Zep", "Zp"],
+        Book::Haggai => &["Haggai", "Hag", "Hg"],
+        Book::Zechariah => &["Zechariah", "Zech", "Zec", "Zc"],
+        Book::Malachi => &["Malachi", "Mal", "Ml"],
+        Book::Matthew => &["Matthew", "Matt", "Mat", "Mt"],
+        Book::Mark => &["Mark", "Mrk", "Mar", "Mk", "Mr"],
+        Book::Luke => &["Luke", "Luk", "Lk"],
+        Book::John => &["John", "Joh", "Jhn", "Jn", "Jh"],
+        Book::Acts => &["Acts", "Acts of the Apostles", "Act", "Ac"],
+        Book::Romans => &["Romans", "Rom", "Ro", "Rm"],
+        Book::FirstCorinthians => &["1 Corinthians", "1 Cor", "1 Co"],
+        Book::SecondCorinthians => &["2 Corinthians", "2 Cor", "2 Co"],
+        Book::Galatians => &["Galatians", "Gal", "Ga"],
+        Book::Ephesians => &["Ephesians", "Ephes", "Eph"],
+        Book::Philippians => &["Philippians", "Phil", "Php", "Pp"],
+        Book::Colossians => &["Colossians", "Col"],
+        Book::FirstThessalonians => &["1 Thessalonians", "1 Thess", "1 Thes", "1 Th"],
+        Book::SecondThessalonians => &["2 Thessalonians", "2 Thess", "2 Thes", "2 Th"],
+        Book::FirstTimothy => &["1 Timothy", "1 Tim", "1 Ti"],
+        Book::SecondTimothy => &["2 Timothy", "2 Tim", "2 Ti"],
+        Book::Titus => &["Titus", "Tit"],
+        Book::Philemon => &["Philemon", "Philem", "Phlm", "Phm"],
+        Book::Hebrews => &["Hebrews", "Heb"],
+        Book::James => &["James", "Jas", "Jm"],
+        Book::FirstPeter => &["1 Peter", "1 Pet", "1 Pe", "1 Pt"],
+        Book::SecondPeter => &["2 Peter", "2 Pet", "2 Pe", "2 Pt"],
+        Book::FirstJohn => &["1 John", "1 Jhn", "1 Jn", "1 Jo"],
+        Book::SecondJohn => &["2 John", "2 Jhn", "2 Jn", "2 Jo"],
+        Book::ThirdJohn => &["3 John", "3 Jhn", "3 Jn", "3 Jo"],
+        Book::Jude => &["Jude", "Jud", "Jd"],
+        Book::Revelation => &["Revelation", "Revelations", "Rev", "Apocalypse", "Re", "Rv"],
+    }
+}
+
+/// Ordinals written before the names of numbered books: `First John`
+#[rustfmt::skip]
+pub static ORDINALS: &[(&str, u8)] = &[
+    ("first", 1), ("1st", 1), ("second", 2), ("2nd", 2),
+    ("third", 3), ("3rd", 3), ("fourth", 4), ("4th", 4),
+];
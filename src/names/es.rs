@@ -0,0 +1,84 @@
+//! Spanish book names
+
+use book::Book;
+
+/// Names and common abbreviations, full name first
+pub fn names(book: Book) -> &'static [&'static str] {
+    match book {
+        Book::Genesis => &["Génesis", "Gén", "Gn"],
+        Book::Exodus => &["Éxodo", "Éx"],
+        Book::Leviticus => &["Levítico", "Lev", "Lv"],
+        Book::Numbers => &["Números", "Núm", "Nm"],
+        Book::Deuteronomy => &["Deuteronomio", "Deut", "Dt"],
+        Book::Joshua => &["Josué", "Jos"],
+        Book::Judges => &["Jueces", "Jue"],
+        Book::Ruth => &["Rut", "Rt"],
+        Book::FirstSamuel => &["1 Samuel", "1 Sam", "1 S"],
+        Book::SecondSamuel => &["2 Samuel", "2 Sam", "2 S"],
+        Book::FirstKings => &["1 Reyes", "1 Re", "1 R"],
+        Book::SecondKings => &["2 Reyes", "2 Re", "2 R"],
+        Book::FirstChronicles => &["1 Crónicas", "1 Cró", "1 Cr"],
+        Book::SecondChronicles => &["2 Crónicas", "2 Cró", "2 Cr"],
+        Book::Ezra => &["Esdras", "Esd"],
+        Book::Nehemiah => &["Nehemías", "Neh"],
+        Book::Esther => &["Ester", "Est"],
+        Book::Job => &["Job", "Jb"],
+        Book::Psalms => &["Salmos", "Sal", "Sl"],
+        Book::Proverbs => &["Proverbios", "Prov", "Pr"],
+        Book::Ecclesiastes => &["Eclesiastés", "Ecl", "Ec"],
+        Book::SongOfSongs => &["Cantar de los Cantares", "Cantares", "Cant", "Cnt"],
+        Book::Isaiah => &["Isaías", "Is"],
+        Book::Jeremiah => &["Jeremías", "Jer"],
+        Book::Lamentations => &["Lamentaciones", "Lam", "Lm"],
+        Book::Ezekiel => &["Ezequiel", "Ez"],
+        Book::Daniel => &["Daniel", "Dn"],
+        Book::Hosea => &["Oseas", "Os"],
+        Book::Joel => &["Joel", "Jl"],
+        Book::Amos => &["Amós", "Am"],
+        Book::Obadiah => &["Abdías", "Abd"],
+        Book::Jonah => &["Jonás", "Jon"],
+        Book::Micah => &["Miqueas", "Miq"],
+        Book::Nahum => &["Nahúm", "Nah"],
+        Book::Habakkuk => &["Habacuc", "Hab"],
+        Book::Zephaniah => &["Sofonías", "Sof"],
+        Book::Haggai => &["Hageo", "Hag"],
+        Book::Zechariah => &["Zacarías", "Zac"],
+        Book::Malachi => &["Malaquías", "Mal"],
+        Book::Matthew => &["Mateo", "Mt"],
+        Book::Mark => &["Marcos", "Mc", "Mr"],
+        Book::Luke => &["Lucas", "Lc"],
+        Book::John => &["Juan", "Jn"],
+        Book::Acts => &["Hechos de los Apóstoles", "Hechos", "Hch"],
+        Book::Romans => &["Romanos", "Rom", "Ro"],
+        Book::FirstCorinthians => &["1 Corintios", "1 Cor", "1 Co"],
+        Book::SecondCorinthians => &["2 Corintios", "2 Cor", "2 Co"],
+        Book::Galatians => &["Gálatas", "Gál", "Gá"],
+        Book::Ephesians => &["Efesios", "Ef"],
+        Book::Philippians => &["Filipenses", "Fil", "Flp"],
+        Book::Colossians => &["Colosenses", "Col"],
+        Book::FirstThessalonians => &["1 Tesalonicenses", "1 Tes", "1 Ts"],
+        Book::SecondThessalonians => &["2 Tesalonicenses", "2 Tes", "2 Ts"],
+        Book::FirstTimothy => &["1 Timoteo", "1 Tim", "1 Ti"],
+        Book::SecondTimothy => &["2 Timoteo", "2 Tim", "2 Ti"],
+        Book::Titus => &["Tito", "Tit"],
+        Book::Philemon => &["Filemón", "Flm"],
+        Book::Hebrews => &["Hebreos", "Heb"],
+        Book::James => &["Santiago", "Stg"],
+        Book::FirstPeter => &["1 Pedro", "1 Pe", "1 P"],
+        Book::SecondPeter => &["2 Pedro", "2 Pe", "2 P"],
+        Book::FirstJohn => &["1 Juan", "1 Jn"],
+        Book::SecondJohn => &["2 Juan", "2 Jn"],
+        Book::ThirdJohn => &["3 Juan", "3 Jn"],
+        Book::Jude => &["Judas", "Jud"],
+        Book::Revelation => &["Apocalipsis", "Apoc", "Ap"],
+    }
+}
+
+/// Ordinals written before the names of numbered books: `Primera Juan`
+#[rustfmt::skip]
+pub static ORDINALS: &[(&str, u8)] = &[
+    ("primero", 1), ("primera", 1), ("primer", 1),
+    ("segundo", 2), ("segunda", 2),
+    ("tercero", 3), ("tercera", 3), ("tercer", 3),
+    ("cuarto", 4), ("cuarta", 4),
+];
@@ -0,0 +1,83 @@
+//! French book names (TOB abbreviations)
+
+use book::Book;
+
+/// Names and common abbreviations, full name first
+pub fn names(book: Book) -> &'static [&'static str] {
+    match book {
+        Book::Genesis => &["Genèse", "Gen", "Gn"],
+        Book::Exodus => &["Exode", "Ex"],
+        Book::Leviticus => &["Lévitique", "Lév", "Lv"],
+        Book::Numbers => &["Nombres", "Nomb", "Nb"],
+        Book::Deuteronomy => &["Deutéronome", "Deut", "Dt"],
+        Book::Joshua => &["Josué", "Jos"],
+        Book::Judges => &["Juges", "Jug", "Jg"],
+        Book::Ruth => &["Ruth", "Rt"],
+        Book::FirstSamuel => &["1 Samuel", "1 Sam", "1 S"],
+        Book::SecondSamuel => &["2 Samuel", "2 Sam", "2 S"],
+        Book::FirstKings => &["1 Rois", "1 R"],
+        Book::SecondKings => &["2 Rois", "2 R"],
+        Book::FirstChronicles => &["1 Chroniques", "1 Chron", "1 Ch"],
+        Book::SecondChronicles => &["2 Chroniques", "2 Chron", "2 Ch"],
+        Book::Ezra => &["Esdras", "Esd"],
+        Book::Nehemiah => &["Néhémie", "Néh", "Ne"],
+        Book::Esther => &["Esther", "Est"],
+        Book::Job => &["Job", "Jb"],
+        Book::Psalms => &["Psaumes", "Psaume", "Ps"],
+        Book::Proverbs => &["Proverbes", "Prov", "Pr"],
+        Book::Ecclesiastes => &["Ecclésiaste", "Qohéleth", "Eccl", "Qo", "Ec"],
+        Book::SongOfSongs => &["Cantique des Cantiques", "Cantique", "Ct"],
+        Book::Isaiah => &["Ésaïe", "Isaïe", "Es", "Is"],
+        Book::Jeremiah => &["Jérémie", "Jér", "Jr"],
+        Book::Lamentations => &["Lamentations", "Lam", "Lm"],
+        Book::Ezekiel => &["Ézéchiel", "Éz", "Ez"],
+        Book::Daniel => &["Daniel", "Dan", "Dn"],
+        Book::Hosea => &["Osée", "Os"],
+        Book::Joel => &["Joël", "Jl"],
+        Book::Amos => &["Amos", "Am"],
+        Book::Obadiah => &["Abdias", "Abd", "Ab"],
+        Book::Jonah => &["Jonas", "Jon"],
+        Book::Micah => &["Michée", "Mi"],
+        Book::Nahum => &["Nahoum", "Nahum", "Na"],
+        Book::Habakkuk => &["Habacuc", "Hab", "Ha"],
+        Book::Zephaniah => &["Sophonie", "Soph", "So"],
+        Book::Haggai => &["Aggée", "Ag"],
+        Book::Zechariah => &["Zacharie", "Zach", "Za"],
+        Book::Malachi => &["Malachie", "Mal", "Ml"],
+        Book::Matthew => &["Matthieu", "Matt", "Mt"],
+        Book::Mark => &["Marc", "Mc"],
+        Book::Luke => &["Luc", "Lc"],
+        Book::John => &["Jean", "Jn"],
+        Book::Acts => &["Actes des Apôtres", "Actes", "Ac"],
+        Book::Romans => &["Romains", "Rom", "Rm"],
+        Book::FirstCorinthians => &["1 Corinthiens", "1 Cor", "1 Co"],
+        Book::SecondCorinthians => &["2 Corinthiens", "2 Cor", "2 Co"],
+        Book::Galatians => &["Galates", "Gal", "Ga"],
+        Book::Ephesians => &["Éphésiens", "Éph", "Ep"],
+        Book::Philippians => &["Philippiens", "Phil", "Ph"],
+        Book::Colossians => &["Colossiens", "Col"],
+        Book::FirstThessalonians => &["1 Thessaloniciens", "1 Thess", "1 Th"],
+        Book::SecondThessalonians => &["2 Thessaloniciens", "2 Thess", "2 Th"],
+        Book::FirstTimothy => &["1 Timothée", "1 Tim", "1 Tm"],
+        Book::SecondTimothy => &["2 Timothée", "2 Tim", "2 Tm"],
+        Book::Titus => &["Tite", "Tt"],
+        Book::Philemon => &["Philémon", "Phm"],
+        Book::Hebrews => &["Hébreux", "Héb", "He"],
+        Book::James => &["Jacques", "Jac", "Jc"],
+        Book::FirstPeter => &["1 Pierre", "1 Pi", "1 P"],
+        Book::SecondPeter => &["2 Pierre", "2 Pi", "2 P"],
+        Book::FirstJohn => &["1 Jean", "1 Jn"],
+        Book::SecondJohn => &["2 Jean", "2 Jn"],
+        Book::ThirdJohn => &["3 Jean", "3 Jn"],
+        Book::Jude => &["Jude", "Jud"],
+        Book::Revelation => &["Apocalypse", "Apoc", "Ap"],
+    }
+}
+
+/// Ordinals written before the names of numbered books: `Première Jean`
+#[rustfmt::skip]
+pub static ORDINALS: &[(&str, u8)] = &[
+    ("premier", 1), ("première", 1), ("1er", 1), ("1re", 1),
+    ("deuxième", 2), ("second", 2), ("seconde", 2), ("2e", 2),
+    ("troisième", 3), ("3e", 3), ("quatrième", 4), ("4e", 4),
+];
@@ -0,0 +1,281 @@
+//! Localized book names
+//!
+//! Every table is compiled in only when its cargo feature is enabled
+//! (`english`, `russian`, `ukrainian`, `german`, `spanish`, `french`).
+
+use std::collections::HashMap;
+
+use book::{Book, BOOKS};
+
+#[cfg(feature = "german")]
+mod de;
+#[cfg(feature = "english")]
+mod en;
+#[cfg(feature = "spanish")]
+mod es;
+#[cfg(feature = "french")]
+mod fr;
+#[cfg(feature = "russian")]
+mod ru;
+#[cfg(feature = "ukrainian")]
+mod uk;
+
+/// Language of book names table
+#[derive(Hash, Eq, PartialEq, Debug, Clone, Copy)]
+pub enum Language {
+    /// English
+    #[cfg(feature = "english")]
+    English,
+    /// Russian
+    #[cfg(feature = "russian")]
+    Russian,
+    /// Ukrainian
+    #[cfg(feature = "ukrainian")]
+    Ukrainian,
+    /// German
+    #[cfg(feature = "german")]
+    German,
+    /// Spanish
+    #[cfg(feature = "spanish")]
+    Spanish,
+    /// French
+    #[cfg(feature = "french")]
+    French,
+}
+
+/// Enabled languages in lookup priority order
+///
+/// When the same abbreviation means different books in different languages
+/// (Russian `1 Цар` is 1 Samuel, Ukrainian `1 Цар` is 1 Kings), the first
+/// language in this list wins. Use `Book::from_name_in` to pick one.
+pub static LANGUAGES: &[Language] = &[
+    #[cfg(feature = "english")]
+    Language::English,
+    #[cfg(feature = "russian")]
+    Language::Russian,
+    #[cfg(feature = "ukrainian")]
+    Language::Ukrainian,
+    #[cfg(feature = "german")]
+    Language::German,
+    #[cfg(feature = "spanish")]
+    Language::Spanish,
+    #[cfg(feature = "french")]
+    Language::French,
+];
+
+impl Language {
+    /// Names and abbreviations of the book, full name first
+    #[allow(unused_variables)]
+    pub fn names(self, book: Book) -> &'static [&'static str] {
+        match self {
+            #[cfg(feature = "english")]
+            Language::English => en::names(book),
+            #[cfg(feature = "russian")]
+            Language::Russian => ru::names(book),
+            #[cfg(feature = "ukrainian")]
+            Language::Ukrainian => uk::names(book),
+            #[cfg(feature = "german")]
+            Language::German => de::names(book),
+            #[cfg(feature = "spanish")]
+            Language::Spanish => es::names(book),
+            #[cfg(feature = "french")]
+            Language::French => fr::names(book),
+        }
+    }
+
+    /// Ordinals written before the names of numbered books, with their numbers
+    pub fn ordinals(self) -> &'static [(&'static str, u8)] {
+        match self {
+            #[cfg(feature = "english")]
+            Language::English => en::ORDINALS,
+            #[cfg(feature = "russian")]
+            Language::Russian => ru::ORDINALS,
+            #[cfg(feature = "ukrainian")]
+            Language::Ukrainian => uk::ORDINALS,
+            #[cfg(feature = "german")]
+            Language::German => de::ORDINALS,
+            #[cfg(feature = "spanish")]
+            Language::Spanish => es::ORDINALS,
+            #[cfg(feature = "french")]
+            Language::French => fr::ORDINALS,
+        }
+    }
+}
+
+/// Roman numbers of numbered books, written before or after the name:
+/// `II Kings`, `Samuel II`
+static ROMAN_NUMERALS: &[(&str, u8)] = &[("i", 1), ("ii", 2), ("iii", 3), ("iv", 4)];
+
+/// Finds book by name in the given language, or in all enabled languages
+pub(crate) fn lookup(name: &str, language: Option<Language>) -> Option<Book> {
+    lazy_static! {
+        static ref TABLES: HashMap<Language, HashMap<String, Book>> = LANGUAGES
+            .iter()
+            .map(|language| {
+                let mut table = HashMap::new();
+                for book in BOOKS.iter() {
+                    for name in language.names(*book) {
+                        table.entry(normalize(name)).or_insert(*book);
+                    }
+                }
+                (*language, table)
+            }).collect();
+    }
+
+    let name = normalize(name);
+    match language {
+        Some(language) => TABLES.get(&language).and_then(|table| table.get(&name)).cloned(),
+        None => LANGUAGES
+            .iter()
+            .filter_map(|language| TABLES.get(language).and_then(|table| table.get(&name)))
+            .next()
+            .cloned(),
+    }
+}
+
+/// Normalizes book token for lookup: lowercase, no dots, spaces, apostrophes
+/// and diacritics, Roman and spelled-out prefixes and Roman suffixes converted
+/// to digit (`"II Ki."`, `"Second Kings"`, `"Kings II"` → `"2ki"`, `"2kings"`)
+fn normalize(name: &str) -> String {
+    let name = name.replace('.', " ").to_lowercase();
+    let mut words: Vec<&str> = name.split_whitespace().collect();
+    let mut result = String::new();
+
+    if words.len() > 1 {
+        let last = words.len() - 1;
+        if let Some(number) = ordinal(words[0]) {
+            result.push_str(&number.to_string());
+            words.remove(0);
+        } else if let Some(number) = roman(words[last]) {
+            result.push_str(&number.to_string());
+            words.remove(last);
+        }
+    }
+
+    for word in words {
+        result.extend(word.chars().filter_map(fold));
+    }
+    result
+}
+
+/// Number of a Roman numeral up to `iv`
+fn roman(word: &str) -> Option<u8> {
+    ROMAN_NUMERALS
+        .iter()
+        .find(|&&(numeral, _)| numeral == word)
+        .map(|&(_, number)| number)
+}
+
+/// Number of a book prefix: Roman numeral or ordinal of any enabled
+/// language (`ii`, `second`, `2nd`, `второе`)
+pub(crate) fn ordinal(word: &str) -> Option<u8> {
+    lazy_static! {
+        static ref ORDINALS: HashMap<String, u8> = LANGUAGES
+            .iter()
+            .flat_map(|language| language.ordinals().iter())
+            .map(|&(word, number)| (word.chars().filter_map(fold).collect(), number))
+            .collect();
+    }
+
+    let word: String = word.to_lowercase().chars().filter_map(fold).collect();
+    roman(&word).or_else(|| ORDINALS.get(&word).cloned())
+}
+
+/// Strips diacritics from letters used in the bundled tables
+fn fold(c: char) -> Option<char> {
+    match c {
+        '\'' | '’' | 'ʼ' => None,
+        'à' | 'á' | 'â' | 'ä' | 'ã' => Some('a'),
+        'ç' => Some('c'),
+        'è' | 'é' | 'ê' | 'ë' => Some('e'),
+        'ì' | 'í' | 'î' | 'ï' => Some('i'),
+        'ñ' => Some('n'),
+        'ò' | 'ó' | 'ô' | 'ö' | 'õ' => Some('o'),
+        'ù' | 'ú' | 'û' | 'ü' => Some('u'),
+        'ё' => Some('е'),
+        c => Some(c),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("II Ki."), "2ki");
+        assert_eq!(normalize("1. Mose"), "1mose");
+        assert_eq!(normalize("Éxodo"), "exodo");
+        assert_eq!(normalize("Об'явлення"), "обявлення");
+        assert_eq!(normalize("Is"), "is");
+        assert_eq!(normalize("Kings II"), "2kings");
+        assert_eq!(normalize("IIII Kings"), "iiiikings");
+        assert_eq!(normalize("I"), "i");
+    }
+
+    #[cfg(all(feature = "english", feature = "russian", feature = "french"))]
+    #[test]
+    fn test_ordinals() {
+        let second_samuel = Some(Book::SecondSamuel);
+        for name in &[
+            "2 Samuel",
+            "II Samuel",
+            "Second Samuel",
+            "2nd Sam",
+            "Samuel II",
+        ] {
+            assert_eq!(lookup(name, None), second_samuel, "{}", name);
+        }
+        assert_eq!(lookup("First John", None), Some(Book::FirstJohn));
+        assert_eq!(lookup("1st Cor.", None), Some(Book::FirstCorinthians));
+        assert_eq!(lookup("Второе Петра", None), Some(Book::SecondPeter));
+        assert_eq!(lookup("Première Jean", None), Some(Book::FirstJohn));
+        assert_eq!(lookup("IIII Kings", None), None);
+        assert_eq!(lookup("Fifth Kings", None), None);
+    }
+
+    #[test]
+    fn test_tables_are_complete() {
+        for language in LANGUAGES {
+            for book in BOOKS.iter() {
+                assert!(!language.names(*book).is_empty());
+                for name in language.names(*book) {
+                    assert!(lookup(name, Some(*language)).is_some(), "{}", name);
+                }
+            }
+        }
+    }
+
+    #[cfg(all(feature = "russian", feature = "ukrainian"))]
+    #[test]
+    fn test_cyrillic() {
+        assert_eq!(lookup("Быт", None), Some(Book::Genesis));
+        assert_eq!(lookup("Исх", None), Some(Book::Exodus));
+        assert_eq!(lookup("1 Пет", None), Some(Book::FirstPeter));
+        assert_eq!(lookup("3 Цар.", None), Some(Book::FirstKings));
+        assert_eq!(lookup("1 Цар", Some(Language::Russian)), Some(Book::FirstSamuel));
+        assert_eq!(lookup("1 Цар", Some(Language::Ukrainian)), Some(Book::FirstKings));
+        assert_eq!(lookup("Бут", None), Some(Book::Genesis));
+    }
+
+    #[cfg(all(feature = "english", feature = "spanish"))]
+    #[test]
+    fn test_priority() {
+        // English `Mc` is Micah, Spanish and French `Mc` is Mark
+        assert_eq!(lookup("Mc", None), Some(Book::Micah));
+        assert_eq!(lookup("Mc", Some(Language::English)), Some(Book::Micah));
+        assert_eq!(lookup("Mc", Some(Language::Spanish)), Some(Book::Mark));
+    }
+
+    #[cfg(all(feature = "german", feature = "spanish", feature = "french"))]
+    #[test]
+    fn test_latin() {
+        assert_eq!(lookup("1. Mose", None), Some(Book::Genesis));
+        assert_eq!(lookup("Offb", None), Some(Book::Revelation));
+        assert_eq!(lookup("Hch", None), Some(Book::Acts));
+        assert_eq!(lookup("Exodo", None), Some(Book::Exodus));
+        assert_eq!(lookup("Mc", Some(Language::Spanish)), Some(Book::Mark));
+        assert_eq!(lookup("Ésaïe", None), Some(Book::Isaiah));
+    }
+}
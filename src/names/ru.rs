@@ -0,0 +1,98 @@
+//! Russian book names (Synodal tradition)
+
+use book::Book;
+
+/// Names and common abbreviations, full name first
+pub fn names(book: Book) -> &'static [&'static str] {
+    match book {
+        Book::Genesis => &["Бытие", "Быт", "Бт"],
+        Book::Exodus => &["Исход", "Исх"],
+        Book::Leviticus => &["Левит", "Лев", "Лв"],
+        Book::Numbers => &["Числа", "Чис", "Чс"],
+        Book::Deuteronomy => &["Второзаконие", "Втор", "Вт"],
+        Book::Joshua => &["Иисус Навин", "Иисуса Навина", "Иис Нав", "Ис Нав", "Нав"],
+        Book::Judges => &["Судьи", "Судей", "Суд", "Сд"],
+        Book::Ruth => &["Руфь", "Руф", "Рф"],
+        Book::FirstSamuel => &[
+            "1 Царств",
+            "1 Книга Царств",
+            "1 Кн. Царств",
+            "1 Цар",
+            "1 Самуила",
+            "1 Сам",
+        ],
+        Book::SecondSamuel => &[
+            "2 Царств",
+            "2 Книга Царств",
+            "2 Кн. Царств",
+            "2 Цар",
+            "2 Самуила",
+            "2 Сам",
+        ],
+        Book::FirstKings => &["3 Царств", "3 Книга Царств", "3 Кн. Царств", "3 Цар"],
+        Book::SecondKings => &["4 Царств", "4 Книга Царств", "4 Кн. Царств", "4 Цар"],
+        Book::FirstChronicles => &["1 Паралипоменон", "1 Пар", "1 Хроник", "1 Хр"],
+        Book::SecondChronicles => &["2 Паралипоменон", "2 Пар", "2 Хроник", "2 Хр"],
+        Book::Ezra => &["Ездра", "1 Ездры", "Ездры", "Езд"],
+        Book::Nehemiah => &["Неемия", "Неем", "Нм"],
+        Book::Esther => &["Есфирь", "Есф", "Ес"],
+        Book::Job => &["Иов"],
+        Book::Psalms => &["Псалтирь", "Псалмы", "Псалом", "Пс"],
+        Book::Proverbs => &["Притчи", "Притч", "Прит", "Пр"],
+        Book::Ecclesiastes => &["Екклесиаст", "Еккл", "Екк", "Ек"],
+        Book::SongOfSongs => &["Песнь Песней", "Песн", "Пес"],
+        Book::Isaiah => &["Исаия", "Иса", "Ис"],
+        Book::Jeremiah => &["Иеремия", "Иер"],
+        Book::Lamentations => &["Плач Иеремии", "Плач", "Пл"],
+        Book::Ezekiel => &["Иезекииль", "Иез"],
+        Book::Daniel => &["Даниил", "Дан", "Дн"],
+        Book::Hosea => &["Осия", "Ос"],
+        Book::Joel => &["Иоиль", "Иоил", "Иол"],
+        Book::Amos => &["Амос", "Ам"],
+        Book::Obadiah => &["Авдий", "Авд"],
+        Book::Jonah => &["Иона", "Ион"],
+        Book::Micah => &["Михей", "Мих", "Мх"],
+        Book::Nahum => &["Наум"],
+        Book::Habakkuk => &["Аввакум", "Авв"],
+        Book::Zephaniah => &["Софония", "Соф"],
+        Book::Haggai => &["Аггей", "Агг"],
+        Book::Zechariah => &["Захария", "Зах"],
+        Book::Malachi => &["Малахия", "Мал"],
+        Book::Matthew => &["От Матфея", "Матфея", "Матф", "Мф", "Мт"],
+        Book::Mark => &["От Марка", "Марка", "Мар", "Мк", "Мр"],
+        Book::Luke => &["От Луки", "Луки", "Лук", "Лк"],
+        Book::John => &["От Иоанна", "Иоанна", "Иоан", "Ин"],
+        Book::Acts => &["Деяния апостолов", "Деяния", "Деян", "Дея"],
+        Book::Romans => &["Римлянам", "Римл", "Рим"],
+        Book::FirstCorinthians => &["1 Коринфянам", "1 Кор"],
+        Book::SecondCorinthians => &["2 Коринфянам", "2 Кор"],
+        Book::Galatians => &["Галатам", "Гал"],
+        Book::Ephesians => &["Ефесянам", "Ефес", "Еф"],
+        Book::Philippians => &["Филиппийцам", "Филип", "Фил", "Флп"],
+        Book::Colossians => &["Колоссянам", "Кол"],
+        Book::FirstThessalonians => &["1 Фессалоникийцам", "1 Фес"],
+        Book::SecondThessalonians => &["2 Фессалоникийцам", "2 Фес"],
+        Book::FirstTimothy => &["1 Тимофею", "1 Тим"],
+        Book::SecondTimothy => &["2 Тимофею", "2 Тим"],
+        Book::Titus => &["Титу", "Тит"],
+        Book::Philemon => &["Филимону", "Филим", "Флм"],
+        Book::Hebrews => &["Евреям", "Евр"],
+        Book::James => &["Иакова", "Иак"],
+        Book::FirstPeter => &["1 Петра", "1 Пет", "1 Пт"],
+        Book::SecondPeter => &["2 Петра", "2 Пет", "2 Пт"],
+        Book::FirstJohn => &["1 Иоанна", "1 Иоан", "1 Ин"],
+        Book::SecondJohn => &["2 Иоанна", "2 Иоан", "2 Ин"],
+        Book::ThirdJohn => &["3 Иоанна", "3 Иоан", "3 Ин"],
+        Book::Jude => &["Иуды", "Иуд"],
+        Book::Revelation => &["Откровение", "Апокалипсис", "Откр", "Отк"],
+    }
+}
+
+/// Ordinals written before the names of numbered books: `Второе Петра`
+#[rustfmt::skip]
+pub static ORDINALS: &[(&str, u8)] = &[
+    ("первая", 1), ("первое", 1), ("первый", 1),
+    ("вторая", 2), ("второе", 2), ("второй", 2),
+    ("третья", 3), ("третье", 3), ("третий", 3),
+    ("четвёртая", 4), ("четвёртое", 4), ("четвёртый", 4),
+];
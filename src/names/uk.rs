@@ -0,0 +1,84 @@
+//! Ukrainian book names (Ogienko tradition)
+
+use book::Book;
+
+/// Names and common abbreviations, full name first
+pub fn names(book: Book) -> &'static [&'static str] {
+    match book {
+        Book::Genesis => &["Буття", "Бут", "Бт"],
+        Book::Exodus => &["Вихід", "Вих"],
+        Book::Leviticus => &["Левит", "Лев"],
+        Book::Numbers => &["Числа", "Чис"],
+        Book::Deuteronomy => &["Повторення Закону", "Повт"],
+        Book::Joshua => &["Ісус Навин", "Іс Нав", "Нав"],
+        Book::Judges => &["Судді", "Суд"],
+        Book::Ruth => &["Рут"],
+        Book::FirstSamuel => &["1 Самуїлова", "1 Сам"],
+        Book::SecondSamuel => &["2 Самуїлова", "2 Сам"],
+        Book::FirstKings => &["1 Царів", "1 Цар"],
+        Book::SecondKings => &["2 Царів", "2 Цар"],
+        Book::FirstChronicles => &["1 Хроніки", "1 Хрон", "1 Хр"],
+        Book::SecondChronicles => &["2 Хроніки", "2 Хрон", "2 Хр"],
+        Book::Ezra => &["Ездра", "Езд"],
+        Book::Nehemiah => &["Неемія", "Неем"],
+        Book::Esther => &["Естер", "Ест"],
+        Book::Job => &["Йов"],
+        Book::Psalms => &["Псалми", "Псалом", "Пс"],
+        Book::Proverbs => &["Приповісті", "Прип", "Пр"],
+        Book::Ecclesiastes => &["Екклезіяст", "Екклезіаст", "Еккл", "Екл"],
+        Book::SongOfSongs => &["Пісня над піснями", "Пісня пісень", "Пісн"],
+        Book::Isaiah => &["Ісая", "Іс"],
+        Book::Jeremiah => &["Єремія", "Єр"],
+        Book::Lamentations => &["Плач Єремії", "Плач"],
+        Book::Ezekiel => &["Єзекіїль", "Єзек", "Єз"],
+        Book::Daniel => &["Даниїл", "Дан"],
+        Book::Hosea => &["Осія", "Ос"],
+        Book::Joel => &["Йоіл", "Йоїл"],
+        Book::Amos => &["Амос", "Ам"],
+        Book::Obadiah => &["Овдій", "Овд"],
+        Book::Jonah => &["Йона", "Йон"],
+        Book::Micah => &["Михей", "Мих"],
+        Book::Nahum => &["Наум"],
+        Book::Habakkuk => &["Авакум", "Авак", "Ав"],
+        Book::Zephaniah => &["Софонія", "Соф"],
+        Book::Haggai => &["Огій", "Ог"],
+        Book::Zechariah => &["Захарія", "Зах"],
+        Book::Malachi => &["Малахія", "Мал"],
+        Book::Matthew => &["Від Матвія", "Матвія", "Матв", "Мт"],
+        Book::Mark => &["Від Марка", "Марка", "Мк", "Мр"],
+        Book::Luke => &["Від Луки", "Луки", "Лук", "Лк"],
+        Book::John => &["Від Івана", "Івана", "Ів", "Ін"],
+        Book::Acts => &["Дії апостолів", "Дії"],
+        Book::Romans => &["Римлян", "Рим"],
+        Book::FirstCorinthians => &["1 Коринтян", "1 Кор"],
+        Book::SecondCorinthians => &["2 Коринтян", "2 Кор"],
+        Book::Galatians => &["Галатів", "Гал"],
+        Book::Ephesians => &["Ефесян", "Еф"],
+        Book::Philippians => &["Филип'ян", "Флп"],
+        Book::Colossians => &["Колосян", "Кол"],
+        Book::FirstThessalonians => &["1 Солунян", "1 Сол"],
+        Book::SecondThessalonians => &["2 Солунян", "2 Сол"],
+        Book::FirstTimothy => &["1 Тимофія", "1 Тим"],
+        Book::SecondTimothy => &["2 Тимофія", "2 Тим"],
+        Book::Titus => &["Тита", "Тит"],
+        Book::Philemon => &["Филимона", "Флм"],
+        Book::Hebrews => &["Євреїв", "Євр"],
+        Book::James => &["Якова", "Як"],
+        Book::FirstPeter => &["1 Петра", "1 Пет"],
+        Book::SecondPeter => &["2 Петра", "2 Пет"],
+        Book::FirstJohn => &["1 Івана", "1 Ів"],
+        Book::SecondJohn => &["2 Івана", "2 Ів"],
+        Book::ThirdJohn => &["3 Івана", "3 Ів"],
+        Book::Jude => &["Юди", "Юд"],
+        Book::Revelation => &["Об'явлення", "Одкровення", "Об", "Одкр"],
+    }
+}
+
+/// Ordinals written before the names of numbered books: `Друге Петра`
+#[rustfmt::skip]
+pub static ORDINALS: &[(&str, u8)] = &[
+    ("перша", 1), ("перше", 1), ("перший", 1),
+    ("друга", 2), ("друге", 2), ("другий", 2),
+    ("третя", 3), ("третє", 3), ("третій", 3),
+    ("четверта", 4), ("четверте", 4), ("четвертий", 4),
+];
@@ -0,0 +1,173 @@
+//! Chapter, verse and list separators
+//!
+//! Separators differ between traditions: `Mt 5:3-7, 9` in English,
+//! `Mt 5,3-7.9` in German, `Gen 1.1` in Dutch and British sources,
+//! `John 3 v 16` in spoken English. A notation tells the parser which
+//! characters and words to read as which separator.
+
+use std::cmp::Reverse;
+
+use regex;
+
+use typography;
+use Language;
+
+/// Separators of a locale
+#[derive(Hash, Eq, PartialEq, Debug, Clone, Copy)]
+pub struct Notation {
+    /// Characters between chapter and verse: `:` in `3:16`
+    pub verse_separators: &'static [char],
+    /// Words between chapter and verse, written with spaces around: `v` in `3 v 16`
+    pub verse_words: &'static [&'static str],
+    /// Characters between items of a list: `,` in `3:16, 18`
+    pub list_separators: &'static [char],
+}
+
+impl Notation {
+    /// English and most other sources: `Mt 5:3-7, 9`
+    pub fn standard() -> Notation {
+        Notation {
+            verse_separators: &[':'],
+            verse_words: &[],
+            list_separators: &[','],
+        }
+    }
+
+    /// German, French and other continental sources: `Mt 5,3-7.9`
+    pub fn german() -> Notation {
+        Notation {
+            verse_separators: &[','],
+            verse_words: &[],
+            list_separators: &['.'],
+        }
+    }
+
+    /// Dutch sources: `Gen 1.1, 3`, colons accepted as well
+    pub fn dutch() -> Notation {
+        Notation {
+            verse_separators: &['.', ':'],
+            verse_words: &[],
+            list_separators: &[','],
+        }
+    }
+
+    /// British sources and sermon notes: `Gen 1.1`, `John 3 v 16`
+    pub fn british() -> Notation {
+        Notation {
+            verse_separators: &['.', ':'],
+            verse_words: &["v", "vv", "vs", "ver"],
+            list_separators: &[','],
+        }
+    }
+
+    /// Usual notation of texts written in the language
+    pub fn for_language(language: Language) -> Notation {
+        match language {
+            #[cfg(feature = "english")]
+            Language::English => Notation::standard(),
+            #[cfg(feature = "russian")]
+            Language::Russian => Notation::standard(),
+            #[cfg(feature = "ukrainian")]
+            Language::Ukrainian => Notation::standard(),
+            #[cfg(feature = "german")]
+            Language::German => Notation::german(),
+            #[cfg(feature = "spanish")]
+            Language::Spanish => Notation::standard(),
+            #[cfg(feature = "french")]
+            Language::French => Notation::german(),
+        }
+    }
+
+    /// Whether the character separates chapter and verse
+    pub(crate) fn is_verse_separator(&self, c: char) -> bool {
+        self.verse_separators.contains(&c)
+    }
+
+    /// Whether the word separates chapter and verse, ignoring case and a trailing dot
+    pub(crate) fn is_verse_word(&self, word: &str) -> bool {
+        let word = word.trim_end_matches('.').to_lowercase();
+        self.verse_words.contains(&word.as_str())
+    }
+
+    /// Whether the character separates list items
+    pub(crate) fn is_list_separator(&self, c: char) -> bool {
+        self.list_separators.contains(&c)
+    }
+
+    /// Pattern matching a chapter and verse separator
+    pub(crate) fn verse_pattern(&self) -> String {
+        let mut alternatives = vec![characters(self.verse_separators)];
+        if !self.verse_words.is_empty() {
+            let mut words: Vec<&str> = self.verse_words.to_vec();
+            words.sort_by_key(|word| Reverse(word.len()));
+            let words: Vec<String> = words.iter().map(|word| regex::escape(word)).collect();
+            alternatives.push(format!(
+                "[\\t\\f\\pZ]+(?i:{})\\.?[\\t\\f\\pZ]*",
+                words.join("|")
+            ));
+        }
+        format!("(?:{})", alternatives.join("|"))
+    }
+
+    /// Pattern matching a list separator
+    pub(crate) fn list_pattern(&self) -> String {
+        characters(self.list_separators)
+    }
+}
+
+impl Default for Notation {
+    fn default() -> Notation {
+        Notation::standard()
+    }
+}
+
+/// Character class of the characters
+fn characters(characters: &[char]) -> String {
+    let escaped: Vec<String> = characters.iter().map(|&c| typography::pattern(c)).collect();
+    format!("[{}]", escaped.concat())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use {parse_with, ParseOptions};
+
+    fn parse(text: &str, notation: Notation) -> Vec<(Vec<u8>, Option<Vec<u8>>)> {
+        let options = ParseOptions {
+            notation,
+            ..ParseOptions::default()
+        };
+        parse_with(text, &options)
+            .into_iter()
+            .flat_map(|reference| reference.locations)
+            .map(|location| (location.chapters, location.verses))
+            .collect()
+    }
+
+    #[test]
+    fn test_german() {
+        assert_eq!(
+            parse("Mt 5,3-7.9", Notation::german()),
+            [(vec![5], Some(vec![3, 4, 5, 6, 7, 9]))]
+        );
+        assert_eq!(
+            parse("Mt 5,3; 6,2", Notation::german()),
+            [(vec![5], Some(vec![3])), (vec![6], Some(vec![2]))]
+        );
+        assert_eq!(parse("Mt 5,3", Notation::standard()), [(vec![5, 3], None)]);
+    }
+
+    #[test]
+    fn test_dotted() {
+        assert_eq!(
+            parse("Gen 1.1, 3", Notation::dutch()),
+            [(vec![1], Some(vec![1, 3]))]
+        );
+        assert_eq!(
+            parse("John 3 v 16 and Rom 8 vv. 28-29", Notation::british()),
+            [(vec![3], Some(vec![16])), (vec![8], Some(vec![28, 29]))]
+        );
+        assert_eq!(parse("John 3 very", Notation::british()), [(vec![3], None)]);
+    }
+}
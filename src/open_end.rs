@@ -0,0 +1,157 @@
+//! Ranges written without their last verse
+//!
+//! Commentaries cite "the verse and the following" (`12:1f.`, `8:28ff.`,
+//! `8:28 сл.`) or "to the end of the chapter" (`119:169-end`). Such ranges
+//! are kept open as written and resolved with the bundled verse counts on
+//! request.
+
+use std::cmp::Reverse;
+
+use {BibleReference, OpenEnd, Segment};
+
+/// Words of open ends, compared ignoring case and a trailing dot
+#[rustfmt::skip]
+static OPEN_END_WORDS: &[(&str, OpenEnd)] = &[
+    // English and German: f., ff.; Spanish and French: s., ss.
+    ("f", OpenEnd::Following), ("s", OpenEnd::Following),
+    ("ff", OpenEnd::FollowingVerses), ("ss", OpenEnd::FollowingVerses),
+    // Russian and Ukrainian: сл. (следующие), дал. (далі)
+    ("сл", OpenEnd::FollowingVerses), ("дал", OpenEnd::FollowingVerses),
+    ("end", OpenEnd::End),
+];
+
+/// Open end written as the word
+pub(crate) fn open_end(word: &str) -> Option<OpenEnd> {
+    let word = word.trim_end_matches('.').to_lowercase();
+    OPEN_END_WORDS
+        .iter()
+        .find(|&&(name, _)| name == word)
+        .map(|&(_, open)| open)
+}
+
+/// Pattern of an optional open end following a verse number: `ff.`, `-end`.
+/// The end of the chapter is only read after a dash
+pub(crate) fn pattern() -> String {
+    let words = |end: bool| {
+        let mut words: Vec<&str> = OPEN_END_WORDS
+            .iter()
+            .filter(|&&(_, open)| (open == OpenEnd::End) == end)
+            .map(|&(word, _)| word)
+            .collect();
+        words.sort_by_key(|word| Reverse(word.len()));
+        words.join("|")
+    };
+    format!(
+        "(?:[\\t\\f\\pZ]*(?i:{})(?:\\.|\\b)|[\\t\\f\\pZ]*{{dash}}[\\t\\f\\pZ]*(?i:{})\\b)?",
+        words(false),
+        words(true)
+    )
+}
+
+impl BibleReference {
+    /// Same reference with open ranges running to their last verse by the
+    /// bundled verse counts: `8:28ff.` becomes `8:28-39`, `12:1f.` becomes
+    /// `12:1-2`. Open ranges of unknown books and chapters are kept
+    pub fn resolve_open_ranges(&self) -> BibleReference {
+        let mut reference = self.clone();
+        let book = match self.book_id {
+            Some(book) => book,
+            None => return reference,
+        };
+
+        for location in &mut reference.locations {
+            let verses = match location.verses {
+                Some(ref mut verses) => verses,
+                None => continue,
+            };
+            location.open_ranges.retain(|open| {
+                let count = match book.verses(open.start.chapter) {
+                    Some(count) => count,
+                    None => return true,
+                };
+                let last = match open.end {
+                    OpenEnd::Following => open.start.verse.saturating_add(1).min(count),
+                    OpenEnd::FollowingVerses | OpenEnd::End => count,
+                };
+                if let Some(index) = verses.iter().position(|&verse| verse == open.start.verse) {
+                    let following = (open.start.verse..last).map(|verse| verse + 1);
+                    verses.splice(index + 1..index + 1, following);
+                }
+                false
+            });
+        }
+        reference.segments = reference
+            .locations
+            .iter()
+            .flat_map(|location| location.segments())
+            .collect();
+        reference
+    }
+
+    /// Segments with open ranges running to their last verse, see
+    /// `resolve_open_ranges`
+    pub(crate) fn resolved_segments(&self) -> Vec<Segment> {
+        if self
+            .locations
+            .iter()
+            .all(|location| location.open_ranges.is_empty())
+        {
+            return self.segments.clone();
+        }
+        self.resolve_open_ranges().segments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use {parse, OpenRange, VersePoint};
+
+    #[test]
+    fn test_parse_open_ranges() {
+        let text = "Rom 8:28ff., Gen 12:1f. and Ps 119:169-end; Мф 5:3 сл.";
+        let refs = parse(text);
+        assert_eq!(refs.len(), 4);
+
+        let location = &refs[0].locations[0];
+        assert_eq!(location.verses, Some(vec![28]));
+        assert_eq!(
+            location.open_ranges,
+            [OpenRange {
+                start: VersePoint {
+                    chapter: 8,
+                    verse: 28
+                },
+                end: OpenEnd::FollowingVerses,
+            }]
+        );
+        assert_eq!(refs[0].location_spans[0].slice(text), "8:28ff.");
+        assert_eq!(refs[1].locations[0].open_ranges[0].end, OpenEnd::Following);
+        assert_eq!(refs[2].locations[0].open_ranges[0].end, OpenEnd::End);
+        assert_eq!(refs[2].span.slice(text), "Ps 119:169-end");
+        assert_eq!(
+            refs[3].locations[0].open_ranges[0].end,
+            OpenEnd::FollowingVerses
+        );
+
+        let refs = parse("Gen 1:1 for Rom 8:28 followed");
+        assert!(refs.iter().all(|r| r.locations[0].open_ranges.is_empty()));
+    }
+
+    #[cfg(feature = "english")]
+    #[test]
+    fn test_resolve_open_ranges() {
+        let resolved = |text: &str| parse(text).remove(0).resolve_open_ranges().to_string();
+        assert_eq!(resolved("Rom 8:28ff."), "Ro 8:28-39");
+        assert_eq!(resolved("Gen 12:1f., 5"), "Ge 12:1-2, 5");
+        assert_eq!(resolved("Ps 119:169-end"), "Ps 119:169-176");
+        assert_eq!(resolved("Rom 16:27f."), "Ro 16:27");
+        assert_eq!(resolved("Notes 1:5ff."), "Notes 1:5ff.");
+
+        let r = parse("Rom 8:28ff.").remove(0).resolve_open_ranges();
+        assert!(r.locations[0].open_ranges.is_empty());
+        assert_eq!(r.iter_verses().count(), 12);
+        assert_eq!(parse("Rom 8:28ff.").remove(0).iter_verses().count(), 12);
+    }
+}
@@ -0,0 +1,320 @@
+//! Canonical order of references
+//!
+//! References are ordered by book in canonical order, then by chapter and
+//! verse of every segment, then by open ranges (`8:28ff.`) and verses cited
+//! in part (`2a`). Books that are not resolved go last, ordered by name.
+//! Spans, and the spelling of resolved book names, are ignored.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use segment::group;
+use {BibleReference, OpenRange, Segment, Span, VerseId, VersePart, VersePoint, VerseSet};
+
+impl BibleReference {
+    /// Book position in the canon, unresolved books after all others
+    fn book_key(&self) -> (usize, &str) {
+        match self.book_id {
+            Some(book) => (book.index(), ""),
+            None => (usize::MAX, self.book.as_str()),
+        }
+    }
+
+    /// Last book, chapter and verse of a range across books, the end of
+    /// the book when not written. References within one book go first
+    fn end_key(&self) -> Option<(usize, &str, u8, u8)> {
+        self.book_end.as_ref().map(|end| {
+            let (index, name) = match end.book_id {
+                Some(book) => (book.index(), ""),
+                None => (usize::MAX, end.book.as_str()),
+            };
+            (
+                index,
+                name,
+                end.chapter.unwrap_or(u8::MAX),
+                end.verse.unwrap_or(u8::MAX),
+            )
+        })
+    }
+
+    /// First and last verse of every segment, whole chapters ending at
+    /// the largest possible verse
+    fn segment_keys(&self) -> Vec<(VersePoint, VersePoint)> {
+        let point = |chapter, verse| VersePoint { chapter, verse };
+        self.segments
+            .iter()
+            .map(|&segment| match segment {
+                Segment::Chapter(chapter) => (point(chapter, 1), point(chapter, u8::MAX)),
+                Segment::Chapters(first, last) => (point(first, 1), point(last, u8::MAX)),
+                Segment::Verse(point) => (point, point),
+                Segment::Verses(range) => (range.start, range.end),
+            }).collect()
+    }
+
+    /// Open ranges of every location in source order
+    fn open_keys(&self) -> Vec<OpenRange> {
+        self.locations
+            .iter()
+            .flat_map(|location| location.open_ranges.iter().cloned())
+            .collect()
+    }
+
+    /// Verses cited in part by every location in source order
+    fn part_keys(&self) -> Vec<(VersePoint, char)> {
+        self.locations
+            .iter()
+            .flat_map(|location| location.parts.iter())
+            .map(|part| (part.point, part.part))
+            .collect()
+    }
+}
+
+impl PartialEq for BibleReference {
+    fn eq(&self, other: &BibleReference) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for BibleReference {}
+
+impl PartialOrd for BibleReference {
+    fn partial_cmp(&self, other: &BibleReference) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BibleReference {
+    fn cmp(&self, other: &BibleReference) -> Ordering {
+        self.book_key()
+            .cmp(&other.book_key())
+            .then_with(|| self.segment_keys().cmp(&other.segment_keys()))
+            .then_with(|| self.open_keys().cmp(&other.open_keys()))
+            .then_with(|| self.part_keys().cmp(&other.part_keys()))
+            .then_with(|| self.end_key().cmp(&other.end_key()))
+    }
+}
+
+/// Sorts references into canonical order and merges duplicate, overlapping
+/// and adjacent ones of the same book (`Gen 1:1-3` and `Gen 1:4` become
+/// `Gen 1:1-4`). Merged references keep the book name of the first one and
+/// have no spans. Open ranges (`8:28ff.`) are merged as resolved by the
+/// bundled verse counts. Verses cited in part (`2a`) keep their suffix
+/// unless they are cited whole too; merge `to_whole_verses` of the
+/// references to drop the suffixes. References with unresolved books, and
+/// ranges across books, are only deduplicated
+pub fn merge(references: &[BibleReference]) -> Vec<BibleReference> {
+    let mut sorted = references.to_vec();
+    sorted.sort();
+    sorted.dedup();
+
+    let mut merged: Vec<BibleReference> = vec![];
+    let mut index = 0;
+    while index < sorted.len() {
+        let book = sorted[index].book_id;
+        let count = match book {
+            Some(_) if sorted[index].book_end.is_none() => sorted[index..]
+                .iter()
+                .take_while(|reference| reference.book_id == book && reference.book_end.is_none())
+                .count(),
+            _ => 1,
+        };
+        let run = &sorted[index..index + count];
+        index += count;
+
+        if book.is_none() || count == 1 {
+            merged.extend(run.iter().cloned());
+            continue;
+        }
+        let name = run[0].book.clone();
+        let parts = cited_parts(run);
+        merged.extend(
+            VerseSet::from_references(run)
+                .to_references()
+                .into_iter()
+                .map(|reference| {
+                    let reference = BibleReference {
+                        book: name.clone(),
+                        ..reference
+                    };
+                    with_parts(reference, &parts)
+                }),
+        );
+    }
+    merged
+}
+
+/// Verses cited in part by the references, with their suffix. Verses also
+/// cited whole, or with another suffix, are left out
+fn cited_parts(references: &[BibleReference]) -> Vec<VersePart> {
+    let parted = |reference: &BibleReference| {
+        reference
+            .locations
+            .iter()
+            .any(|location| !location.parts.is_empty())
+    };
+    if !references.iter().any(parted) {
+        return vec![];
+    }
+
+    let mut cited: BTreeMap<VersePoint, Option<char>> = BTreeMap::new();
+    for reference in references {
+        let parts: Vec<VersePart> = reference
+            .locations
+            .iter()
+            .flat_map(|location| location.parts.iter().cloned())
+            .collect();
+        for point in reference.iter_verses() {
+            let part = parts
+                .iter()
+                .find(|part| part.point == point)
+                .map(|part| part.part);
+            cited
+                .entry(point)
+                .and_modify(|cited| {
+                    if *cited != part {
+                        *cited = None
+                    }
+                }).or_insert(part);
+        }
+    }
+    cited
+        .into_iter()
+        .filter_map(|(point, part)| part.map(|part| VersePart { point, part }))
+        .collect()
+}
+
+/// Reference with verses cited in part split out of the segments around
+/// them, so that they start or end a range, and marked in its locations
+fn with_parts(mut reference: BibleReference, parts: &[VersePart]) -> BibleReference {
+    let book = match reference.book_id {
+        Some(book) if !parts.is_empty() => book,
+        _ => return reference,
+    };
+    let step = |point: VersePoint, next: bool| {
+        let id = VerseId::new(book, point);
+        let id = if next { id.next() } else { id.previous() };
+        id.filter(|id| id.book() == book).map(VerseId::point)
+    };
+
+    let mut segments: Vec<(Segment, Span)> = vec![];
+    for &segment in &reference.segments {
+        let (start, end) = match segment.bounds(Some(book)) {
+            Some(bounds) => bounds,
+            None => {
+                segments.push((segment, Span::default()));
+                continue;
+            }
+        };
+        let inside: Vec<VersePoint> = parts
+            .iter()
+            .map(|part| part.point)
+            .filter(|&point| start <= point && point <= end)
+            .collect();
+        if inside.is_empty() {
+            segments.push((segment, Span::default()));
+            continue;
+        }
+
+        let mut first = Some(start);
+        for point in inside {
+            if let (Some(first), Some(before)) = (first, step(point, false)) {
+                if first < point {
+                    segments.push((Segment::verses(first, before), Span::default()));
+                }
+            }
+            segments.push((Segment::Verse(point), Span::default()));
+            first = step(point, true);
+        }
+        if let Some(first) = first.filter(|&first| first <= end) {
+            segments.push((Segment::verses(first, end), Span::default()));
+        }
+    }
+
+    reference.segments = segments.iter().map(|&(segment, _)| segment).collect();
+    reference.locations = group(&segments)
+        .into_iter()
+        .map(|(mut location, _)| {
+            location.parts = parts
+                .iter()
+                .cloned()
+                .filter(|part| {
+                    location.chapters == [part.point.chapter]
+                        && location
+                            .verses
+                            .as_ref()
+                            .is_some_and(|verses| verses.contains(&part.point.verse))
+                }).collect();
+            location
+        }).collect();
+    reference
+}
+
+#[cfg(all(test, feature = "english"))]
+mod tests {
+
+    use super::*;
+    use {parse, BookStyle, Format};
+
+    fn texts(references: &[BibleReference]) -> Vec<String> {
+        let format = Format {
+            book: BookStyle::AsWritten,
+            ..Format::default()
+        };
+        references.iter().map(|r| r.format(&format)).collect()
+    }
+
+    #[test]
+    fn test_order() {
+        let mut refs = parse("Rev 2, Gen 1, Mark 4; Notes 3; Gen 1:5; Acts 1 Gen 1:2");
+        refs.sort();
+        assert_eq!(
+            texts(&refs),
+            ["Gen 1", "Gen 1:2", "Gen 1:5", "Mark 4", "Acts 1", "Rev 2", "Notes 3"]
+        );
+
+        assert_eq!(parse("Gen 1:1").remove(0), parse("Genesis 1:1").remove(0));
+        assert!(parse("Gen 1:1").remove(0) < parse("Gen 1:1-2").remove(0));
+        assert!(parse("Gen 50").remove(0) < parse("Exod 1").remove(0));
+        assert_ne!(parse("Rom 8:28ff.").remove(0), parse("Rom 8:28").remove(0));
+        assert!(parse("Rom 8:28").remove(0) < parse("Rom 8:28ff.").remove(0));
+        assert_ne!(parse("Gen 1:2a").remove(0), parse("Gen 1:2").remove(0));
+        assert_eq!(
+            parse("Gen 1:2a").remove(0).to_whole_verses(),
+            parse("Gen 1:2").remove(0)
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let refs = parse("Gen 1:4; Gen 1:1-3; Exod 2; Gen 1:1-3; Ex 1; Notes 1; Notes 1");
+        assert_eq!(texts(&merge(&refs)), ["Gen 1:1-4", "Ex 1-2", "Notes 1"]);
+
+        let refs = parse("Rom 8:28-39; Rom 8:1-27; Rom 9:3");
+        assert_eq!(texts(&merge(&refs)), ["Rom 8, 9:3"]);
+
+        let refs = parse("Rom 8:28ff.; Rom 9:1; Rom 8:28ff.");
+        assert_eq!(texts(&merge(&refs)), ["Rom 8:28-9:1"]);
+        let refs = parse("Rom 8:28ff.; Rom 8:28ff.");
+        assert_eq!(texts(&merge(&refs)), ["Rom 8:28ff."]);
+    }
+
+    #[test]
+    fn test_merge_parts() {
+        let refs = parse("Gen 1:2a; Gen 1:5");
+        assert_eq!(texts(&merge(&refs)), ["Gen 1:2a, 5"]);
+        let refs = parse("Gen 1:3; Gen 1:2a; Gen 1:1");
+        assert_eq!(texts(&merge(&refs)), ["Gen 1:1-2a, 3"]);
+        let refs = parse("Gen 1:2a; Gen 1:1-3; Gen 1:4b; Gen 1:4c");
+        assert_eq!(texts(&merge(&refs)), ["Gen 1:1-4"]);
+        let refs = parse("Gen 1:30-2:3; Gen 2:4a; Gen 1; Gen 1:31b");
+        assert_eq!(texts(&merge(&refs)), ["Gen 1:1-2:3, 2:4a"]);
+        let refs = parse("Ps 23:1b-3a; Ps 23:5");
+        assert_eq!(texts(&merge(&refs)), ["Ps 23:1b-3a, 5"]);
+
+        let whole: Vec<BibleReference> = parse("Gen 1:2a; Gen 1:5")
+            .iter()
+            .map(BibleReference::to_whole_verses)
+            .collect();
+        assert_eq!(texts(&merge(&whole)), ["Gen 1:2, 5"]);
+    }
+}
@@ -0,0 +1,577 @@
+//! OSIS references (`Gen.1.1-Gen.1.3 Ps.23`)
+//!
+//! Only plain references are supported: work prefixes (`KJV:`) are
+//! rejected, and grains only mark verse parts (`Gen.1.2!a`). Ranges crossing
+//! book boundaries (`Matt.27-Mark.2`) are read into `book_end`.
+
+use segment::{group, Segment};
+use {
+    BibleReference, Book, BookEnd, ParseError, Span, Typography, VersePart, VersePoint, VerseRange,
+    BOOKS,
+};
+
+impl Book {
+    /// OSIS book identifier (`Gen`, `1Kgs`, `Phlm`)
+    pub fn osis_id(self) -> &'static str {
+        match self {
+            Book::Genesis => "Gen",
+            Book::Exodus => "Exod",
+            Book::Leviticus => "Lev",
+            Book::Numbers => "Num",
+            Book::Deuteronomy => "Deut",
+            Book::Joshua => "Josh",
+            Book::Judges => "Judg",
+            Book::Ruth => "Ruth",
+            Book::FirstSamuel => "1Sam",
+            Book::SecondSamuel => "2Sam",
+            Book::FirstKings => "1Kgs",
+            Book::SecondKings => "2Kgs",
+            Book::FirstChronicles => "1Chr",
+            Book::SecondChronicles => "2Chr",
+            Book::Ezra => "Ezra",
+            Book::Nehemiah => "Neh",
+            Book::Esther => "Esth",
+            Book::Job => "Job",
+            Book::Psalms => "Ps",
+            Book::Proverbs => "Prov",
+            Book::Ecclesiastes => "Eccl",
+            Book::SongOfSongs => "Song",
+            Book::Isaiah => "Isa",
+            Book::Jeremiah => "Jer",
+            Book::Lamentations => "Lam",
+            Book::Ezekiel => "Ezek",
+            Book::Daniel => "Dan",
+            Book::Hosea => "Hos",
+            Book::Joel => "Joel",
+            Book::Amos => "Amos",
+            Book::Obadiah => "Obad",
+            Book::Jonah => "Jonah",
+            Book::Micah => "Mic",
+            Book::Nahum => "Nah",
+            Book::Habakkuk => "Hab",
+            Book::Zephaniah => "Zeph",
+            Book::Haggai => "Hag",
+            Book::Zechariah => "Zech",
+            Book::Malachi => "Mal",
+            Book::Matthew => "Matt",
+            Book::Mark => "Mark",
+            Book::Luke => "Luke",
+            Book::John => "John",
+            Book::Acts => "Acts",
+            Book::Romans => "Rom",
+            Book::FirstCorinthians => "1Cor",
+            Book::SecondCorinthians => "2Cor",
+            Book::Galatians => "Gal",
+            Book::Ephesians => "Eph",
+            Book::Philippians => "Phil",
+            Book::Colossians => "Col",
+            Book::FirstThessalonians => "1Thess",
+            Book::SecondThessalonians => "2Thess",
+            Book::FirstTimothy => "1Tim",
+            Book::SecondTimothy => "2Tim",
+            Book::Titus => "Titus",
+            Book::Philemon => "Phlm",
+            Book::Hebrews => "Heb",
+            Book::James => "Jas",
+            Book::FirstPeter => "1Pet",
+            Book::SecondPeter => "2Pet",
+            Book::FirstJohn => "1John",
+            Book::SecondJohn => "2John",
+            Book::ThirdJohn => "3John",
+            Book::Jude => "Jude",
+            Book::Revelation => "Rev",
+        }
+    }
+
+    /// Resolves OSIS book identifier, case sensitive
+    pub fn from_osis_id(id: &str) -> Option<Book> {
+        BOOKS.iter().cloned().find(|book| book.osis_id() == id)
+    }
+}
+
+impl BibleReference {
+    /// Serializes reference as OSIS `osisRef`, e.g. `"Gen.1.1-Gen.1.3 Gen.2"`,
+    /// with verse parts as grains: `"Gen.1.2!a"`. Reference covering every
+    /// chapter is written as the book alone, and a range across books as one
+    /// range: `"Matt.27-Mark.2"`. Open ranges are written to their last verse
+    /// (`"Rom.8.28-Rom.8.39"`).
+    /// Returns `None` if a book or the end of an open range is not resolved
+    pub fn to_osis(&self) -> Option<String> {
+        let book = self.book_id?;
+        if self
+            .locations
+            .iter()
+            .flat_map(|location| &location.open_ranges)
+            .any(|open| book.verses(open.start.chapter).is_none())
+        {
+            return None;
+        }
+        let id = book.osis_id();
+        let parts: Vec<VersePart> = self
+            .locations
+            .iter()
+            .flat_map(|location| location.parts.iter().cloned())
+            .collect();
+        let point = |point: VersePoint| {
+            let grain = parts
+                .iter()
+                .find(|part| part.point == point)
+                .map_or(String::new(), |part| format!("!{}", part.part));
+            format!("{}.{}.{}{}", id, point.chapter, point.verse, grain)
+        };
+
+        if let Some(ref end) = self.book_end {
+            let start = match self.segments.first() {
+                Some(&Segment::Chapter(chapter)) | Some(&Segment::Chapters(chapter, _)) => {
+                    format!("{}.{}", id, chapter)
+                }
+                Some(&Segment::Verse(start)) | Some(&Segment::Verses(VerseRange { start, .. })) => {
+                    point(start)
+                }
+                None => id.to_string(),
+            };
+            let end_id = end.book_id?.osis_id();
+            let end = match (end.chapter, end.verse) {
+                (Some(chapter), Some(verse)) => format!("{}.{}.{}", end_id, chapter, verse),
+                (Some(chapter), None) => format!("{}.{}", end_id, chapter),
+                (None, _) => end_id.to_string(),
+            };
+            return Some(format!("{}-{}", start, end));
+        }
+        if self.segments.is_empty() {
+            return Some(id.to_string());
+        }
+
+        let items: Vec<String> = self
+            .resolved_segments()
+            .iter()
+            .map(|&segment| match segment {
+                Segment::Chapters(1, last) if last == book.chapters() => id.to_string(),
+                Segment::Chapter(1) if book.chapters() == 1 => id.to_string(),
+                Segment::Chapter(chapter) => format!("{}.{}", id, chapter),
+                Segment::Chapters(first, last) => format!("{}.{}-{}.{}", id, first, id, last),
+                Segment::Verse(start) => point(start),
+                Segment::Verses(range) => format!("{}-{}", point(range.start), point(range.end)),
+            }).collect();
+        Some(items.join(" "))
+    }
+}
+
+/// Parses OSIS `osisRef` into references, one for every run of the same
+/// book and one for every range across books
+pub fn parse_osis(string: &str) -> Result<Vec<BibleReference>, ParseError> {
+    let mut items: Vec<(Item, Span)> = Vec::new();
+    let mut offset = 0;
+    for word in string.split_whitespace() {
+        let start = offset + string[offset..].find(word).unwrap_or(0);
+        offset = start + word.len();
+        let item = parse_item(word).map_err(|error| error.shift(start))?;
+        items.push((item, Span { start, end: offset }));
+    }
+    if items.is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    let mut references: Vec<BibleReference> = Vec::new();
+    let mut index = 0;
+    while index < items.len() {
+        let book = items[index].0.book;
+        let count = match items[index].0.end {
+            Some(_) => 1,
+            None => items[index..]
+                .iter()
+                .take_while(|&(item, _)| item.book == book && item.end.is_none())
+                .count(),
+        };
+        let run = &items[index..index + count];
+        index += count;
+
+        let segments: Vec<(Segment, Span)> = run
+            .iter()
+            .filter_map(|(item, span)| item.segment.map(|segment| (segment, *span)))
+            .collect();
+        let (mut locations, location_spans): (Vec<_>, Vec<Span>) =
+            group(&segments).into_iter().unzip();
+        for (item, span) in run {
+            let index = location_spans
+                .iter()
+                .position(|location| location.start <= span.start && span.end <= location.end);
+            if let Some(index) = index {
+                locations[index].parts.extend(item.parts.iter().cloned());
+            }
+        }
+        let first = run[0].1;
+        let book_end = run[0]
+            .0
+            .end
+            .map(|(book_id, (chapter, verse), start)| BookEnd {
+                book: book_id.osis_id().to_string(),
+                book_id: Some(book_id),
+                chapter,
+                verse,
+                book_span: Span {
+                    start: first.start + start,
+                    end: first.start + start + book_id.osis_id().len(),
+                },
+            });
+        references.push(BibleReference {
+            book: book.osis_id().to_string(),
+            book_id: Some(book),
+            locations,
+            segments: segments.iter().map(|&(segment, _)| segment).collect(),
+            span: Span {
+                start: first.start,
+                end: run[count - 1].1.end,
+            },
+            book_span: Span {
+                start: first.start,
+                end: first.start + book.osis_id().len(),
+            },
+            location_spans,
+            inferred: false,
+            typography: Typography::default(),
+            book_end,
+        });
+    }
+    Ok(references)
+}
+
+/// Chapter and verse of `Book.C.V`, when present
+type OsisPoint = (Option<u8>, Option<u8>);
+
+/// Word of `osisRef`
+struct Item {
+    /// First book
+    book: Book,
+    /// Chapters and verses of the book, `None` for the whole of it when
+    /// the range runs into another book
+    segment: Option<Segment>,
+    /// Last book of a range across books, with its chapter and verse and
+    /// its offset in the word
+    end: Option<(Book, OsisPoint, usize)>,
+    /// Verses cited in part, marked with grains
+    parts: Vec<VersePart>,
+}
+
+/// Reads `Book`, `Book.C`, `Book.C.V` or a range of two of them
+fn parse_item(word: &str) -> Result<Item, ParseError> {
+    let (first, last) = match word.find('-') {
+        Some(dash) => (&word[..dash], Some((dash + 1, &word[dash + 1..]))),
+        None => (word, None),
+    };
+
+    let whole = Span {
+        start: 0,
+        end: word.len(),
+    };
+    let (book, start, start_grain) = parse_point(first, 0)?;
+    let (start_chapter, start_verse) = start;
+    let mut end_grain = None;
+    let (end_chapter, end_verse) = match last {
+        None => start,
+        Some((offset, last)) => {
+            let (end_book, end, grain) = parse_point(last, offset)?;
+            end_grain = grain;
+            if end_book.index() < book.index() {
+                return Err(ParseError::DescendingRange(whole));
+            }
+            if end_book != book {
+                let segment = match start {
+                    (Some(chapter), Some(verse)) => {
+                        Some(Segment::Verse(VersePoint { chapter, verse }))
+                    }
+                    (Some(chapter), None) => Some(Segment::Chapter(chapter)),
+                    (None, _) => None,
+                };
+                let parts = match segment {
+                    Some(Segment::Verse(point)) => start_grain
+                        .map(|part| VersePart { point, part })
+                        .into_iter()
+                        .collect(),
+                    _ => vec![],
+                };
+                return Ok(Item {
+                    book,
+                    segment,
+                    end: Some((end_book, end, offset)),
+                    parts,
+                });
+            }
+            end
+        }
+    };
+
+    let first_chapter = start_chapter.unwrap_or(1);
+    let last_chapter = end_chapter.unwrap_or_else(|| book.chapters());
+    let mut parts = vec![];
+    let segment = match (start_verse, end_verse) {
+        (None, None) if first_chapter == last_chapter => Segment::Chapter(first_chapter),
+        (None, None) => Segment::Chapters(first_chapter, last_chapter),
+        (start_verse, end_verse) => {
+            let end_verse = match end_verse {
+                Some(verse) => verse,
+                None => book
+                    .verses(last_chapter)
+                    .ok_or(ParseError::NumberOutOfRange(whole))?,
+            };
+            let start = VersePoint {
+                chapter: first_chapter,
+                verse: start_verse.unwrap_or(1),
+            };
+            let end = VersePoint {
+                chapter: last_chapter,
+                verse: end_verse,
+            };
+            parts.extend(start_grain.map(|part| VersePart { point: start, part }));
+            parts.extend(
+                end_grain
+                    .filter(|_| start < end)
+                    .map(|part| VersePart { point: end, part }),
+            );
+            if start == end {
+                Segment::Verse(start)
+            } else {
+                Segment::Verses(VerseRange { start, end })
+            }
+        }
+    };
+
+    let descending = match segment {
+        Segment::Chapters(first, last) => first > last,
+        Segment::Verses(range) => range.start > range.end,
+        _ => false,
+    };
+    if descending {
+        return Err(ParseError::DescendingRange(whole));
+    }
+    Ok(Item {
+        book,
+        segment: Some(segment),
+        end: None,
+        parts,
+    })
+}
+
+/// Reads `Book`, `Book.C`, `Book.C.V` or `Book.C.V!a` starting at `offset`
+/// of the item, with the letter of the grain
+fn parse_point(text: &str, offset: usize) -> Result<(Book, OsisPoint, Option<char>), ParseError> {
+    let (text, grain) = match text.find('!') {
+        Some(mark) => (&text[..mark], Some(&text[mark + 1..])),
+        None => (text, None),
+    };
+    let mut parts = text.split('.');
+    let id = parts.next().unwrap_or("");
+    let book = Book::from_osis_id(id).ok_or_else(|| ParseError::UnknownBook(id.to_string()))?;
+
+    let mut numbers = vec![];
+    let mut start = offset + id.len();
+    for part in parts {
+        let span = Span {
+            start: start + 1,
+            end: start + 1 + part.len(),
+        };
+        start = span.end;
+        if numbers.len() == 2 || part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseError::UnexpectedText(span));
+        }
+        match part.parse::<u8>() {
+            Ok(number) if number > 0 => numbers.push(number),
+            _ => return Err(ParseError::NumberOutOfRange(span)),
+        }
+    }
+
+    // Only single letters after a verse are read, as the parts of `2a`
+    let part = match grain {
+        None => None,
+        Some(grain) => {
+            let mut letters = grain.chars();
+            match (letters.next(), letters.next()) {
+                (Some(letter), None) if numbers.len() == 2 && letter.is_ascii_lowercase() => {
+                    Some(letter)
+                }
+                _ => {
+                    return Err(ParseError::UnexpectedText(Span {
+                        start,
+                        end: start + 1 + grain.len(),
+                    }))
+                }
+            }
+        }
+    };
+    Ok((book, (numbers.first().cloned(), numbers.get(1).cloned()), part))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    #[cfg(feature = "english")]
+    use {parse, parse_with, ParseOptions};
+
+    #[test]
+    fn test_osis_ids() {
+        for book in BOOKS.iter() {
+            assert_eq!(Book::from_osis_id(book.osis_id()), Some(*book));
+        }
+        assert_eq!(Book::from_osis_id("gen"), None);
+    }
+
+    #[cfg(feature = "english")]
+    #[test]
+    fn test_to_osis() {
+        let osis = |text: &str| parse(text).remove(0).to_osis().unwrap();
+        assert_eq!(osis("Gen 1:1-3"), "Gen.1.1-Gen.1.3");
+        assert_eq!(osis("Gen 1:1, 3-4"), "Gen.1.1 Gen.1.3-Gen.1.4");
+        assert_eq!(osis("1 Kings 3-5, 7"), "1Kgs.3-1Kgs.5 1Kgs.7");
+        assert_eq!(osis("John 1:51-2:3"), "John.1.51-John.2.3");
+        assert_eq!(osis("Ruth 1-4"), "Ruth");
+        assert_eq!(osis("Jude 5"), "Jude.1.5");
+        assert_eq!(parse_osis("Jude").unwrap()[0].to_osis().unwrap(), "Jude");
+        assert_eq!(parse("Notes 1:1").remove(0).to_osis(), None);
+
+        assert_eq!(osis("Rom 8:28ff."), "Rom.8.28-Rom.8.39");
+        assert_eq!(osis("Gen 12:1f., 5"), "Gen.12.1-Gen.12.2 Gen.12.5");
+        assert_eq!(osis("Ps 119:169-end"), "Ps.119.169-Ps.119.176");
+        assert_eq!(parse("Ps 151:1ff.").remove(0).to_osis(), None);
+
+        assert_eq!(osis("Gen 1:2a"), "Gen.1.2!a");
+        assert_eq!(osis("Gen 1:1b-3a, 5"), "Gen.1.1!b-Gen.1.3!a Gen.1.5");
+    }
+
+    #[cfg(feature = "english")]
+    #[test]
+    fn test_parse_osis() {
+        let refs = parse_osis("Gen.1.1-Gen.1.3 Gen.2 Ps.23 Ruth").unwrap();
+        assert_eq!(refs.len(), 3);
+        assert_eq!(refs[0].book_id, Some(Book::Genesis));
+        assert_eq!(refs[0].locations[0].verses, Some(vec![1, 2, 3]));
+        assert_eq!(refs[0].locations[1].chapters, [2]);
+        assert_eq!(refs[0].location_spans[1], Span { start: 16, end: 21 });
+        assert_eq!(refs[1].span, Span { start: 22, end: 27 });
+        assert_eq!(refs[2].segments, [Segment::Chapters(1, 4)]);
+
+        let refs = parse_osis("Gen.1.30-Gen.2").unwrap();
+        assert_eq!(
+            refs[0].segments[0],
+            Segment::Verses(VerseRange {
+                start: VersePoint {
+                    chapter: 1,
+                    verse: 30
+                },
+                end: VersePoint {
+                    chapter: 2,
+                    verse: 25
+                },
+            })
+        );
+
+        for text in &[
+            "Gen 1:1-3, 5:2, 7",
+            "Ps 119, 121",
+            "John 1:51-2:3, 5",
+            "Ruth 1-4",
+        ] {
+            let reference = parse(text).remove(0);
+            let osis = reference.to_osis().unwrap();
+            let parsed = parse_osis(&osis).unwrap().remove(0);
+            assert_eq!(parsed.segments, reference.segments, "{}", osis);
+            assert_eq!(parsed.locations, reference.locations, "{}", osis);
+        }
+
+        for text in &["Gen 1:2a", "Gen 1:1b-3a, 5", "John 1:51b-2:3"] {
+            let reference = parse(text).remove(0);
+            let osis = reference.to_osis().unwrap();
+            let parsed = parse_osis(&osis).unwrap().remove(0);
+            assert_eq!(parsed.locations, reference.locations, "{}", osis);
+        }
+
+        for text in &["Rom 8:28ff.", "Gen 12:1f., 5"] {
+            let reference = parse(text).remove(0).resolve_open_ranges();
+            let osis = reference.to_osis().unwrap();
+            let parsed = parse_osis(&osis).unwrap().remove(0);
+            assert_eq!(parsed.segments, reference.segments, "{}", osis);
+            assert_eq!(parsed.to_osis().unwrap(), osis);
+        }
+    }
+
+    #[cfg(feature = "english")]
+    #[test]
+    fn test_parse_osis_book_ranges() {
+        let refs = parse_osis("Gen-Deut Matt.27-Mark.2 Ps.1").unwrap();
+        assert_eq!(refs.len(), 3);
+        assert!(refs[0].is_whole_book());
+        let end = refs[1].book_end.as_ref().unwrap();
+        assert_eq!(
+            (end.book_id, end.chapter, end.verse),
+            (Some(Book::Mark), Some(2), None)
+        );
+        assert_eq!(end.book_span, Span { start: 17, end: 21 });
+        assert_eq!(refs[1].segments, [Segment::Chapter(27)]);
+        assert_eq!(refs[2].book_id, Some(Book::Psalms));
+
+        let options = ParseOptions {
+            whole_books: true,
+            ..ParseOptions::default()
+        };
+        for text in &[
+            "Gen–Deut",
+            "Matt 27 – Mark 2",
+            "Matt 28:19-Mark 1:3",
+            "Ruth 2-Esth",
+        ] {
+            let reference = parse_with(text, &options).remove(0);
+            let osis = reference.to_osis().unwrap();
+            let parsed = parse_osis(&osis).unwrap().remove(0);
+            assert_eq!(parsed.segments, reference.segments, "{}", osis);
+            let end = |r: &BibleReference| {
+                r.book_end
+                    .as_ref()
+                    .map(|end| (end.book_id, end.chapter, end.verse))
+            };
+            assert_eq!(end(&parsed), end(&reference), "{}", osis);
+            assert_eq!(parsed.to_osis().unwrap(), osis);
+        }
+
+        let mut reference = parse_osis("Matt.27-Mark.2").unwrap().remove(0);
+        reference.segments = vec![Segment::Verses(VerseRange {
+            start: VersePoint {
+                chapter: 27,
+                verse: 3,
+            },
+            end: VersePoint {
+                chapter: 27,
+                verse: 5,
+            },
+        })];
+        assert_eq!(reference.to_osis().unwrap(), "Matt.27.3-Mark.2");
+    }
+
+    #[test]
+    fn test_parse_osis_errors() {
+        assert_eq!(parse_osis(" ").err(), Some(ParseError::Empty));
+        assert_eq!(
+            parse_osis("Gen.1.1 Foo.1").err(),
+            Some(ParseError::UnknownBook("Foo".to_string()))
+        );
+        assert_eq!(
+            parse_osis("Exod.2-Gen.1").err(),
+            Some(ParseError::DescendingRange(Span { start: 0, end: 12 }))
+        );
+        assert_eq!(
+            parse_osis("Gen.1.1.1").err(),
+            Some(ParseError::UnexpectedText(Span { start: 8, end: 9 }))
+        );
+        assert_eq!(
+            parse_osis("Gen.3-Gen.1").err(),
+            Some(ParseError::DescendingRange(Span { start: 0, end: 11 }))
+        );
+        assert_eq!(
+            parse_osis("Gen.1!a").err(),
+            Some(ParseError::UnexpectedText(Span { start: 5, end: 7 }))
+        );
+        assert_eq!(
+            parse_osis("Gen.1.2!ab").err(),
+            Some(ParseError::UnexpectedText(Span { start: 7, end: 10 }))
+        );
+    }
+}
@@ -0,0 +1,295 @@
+//! Compact location model
+//!
+//! Segments keep ranges as they were written (`1-3` is one segment, `1,2,3`
+//! three), so long ranges take no memory. Verses are expanded lazily.
+
+use std::iter;
+
+use {BibleReference, Book, Span, VerseLocation, VersePoint, VerseRange};
+
+/// Single item or range of chapters or verses
+#[derive(Hash, Eq, PartialEq, Debug, Clone, Copy)]
+pub enum Segment {
+    /// Whole chapter: `5`
+    Chapter(u8),
+    /// Whole chapters `first..=last`: `5-8`
+    Chapters(u8, u8),
+    /// Single verse: `3:16`
+    Verse(VersePoint),
+    /// Verses from start to end, possibly crossing chapter boundary:
+    /// `3:16-18`, `1:30-2:3`
+    Verses(VerseRange),
+}
+
+impl Segment {
+    /// Chapters segment, `first` alone when the range is descending
+    pub(crate) fn chapters(first: u8, last: u8) -> Segment {
+        if first < last {
+            Segment::Chapters(first, last)
+        } else {
+            Segment::Chapter(first)
+        }
+    }
+
+    /// Verses segment, `start` alone when the range is descending
+    pub(crate) fn verses(start: VersePoint, end: VersePoint) -> Segment {
+        if start < end {
+            Segment::Verses(VerseRange { start, end })
+        } else {
+            Segment::Verse(start)
+        }
+    }
+
+    /// First and last verse, `None` when the last verse is not known
+    pub(crate) fn bounds(self, book: Option<Book>) -> Option<(VersePoint, VersePoint)> {
+        let last = |chapter| {
+            book.and_then(|book| book.verses(chapter))
+                .map(|verse| VersePoint { chapter, verse })
+        };
+        let first = |chapter| VersePoint { chapter, verse: 1 };
+        match self {
+            Segment::Chapter(chapter) => last(chapter).map(|end| (first(chapter), end)),
+            Segment::Chapters(start, end) => last(end).map(|end| (first(start), end)),
+            Segment::Verse(point) => Some((point, point)),
+            Segment::Verses(range) => Some((range.start, range.end)),
+        }
+    }
+
+    /// Iterates over every verse of the segment in order, using the bundled
+    /// verse counts of the book. Whole chapters of an unknown book are empty
+    pub fn iter_verses(self, book: Option<Book>) -> impl Iterator<Item = VersePoint> {
+        let bounds = self.bounds(book);
+        let start = bounds.map(|(start, _)| start);
+        iter::successors(start, move |&point| {
+            let (_, end) = bounds?;
+            if point >= end {
+                return None;
+            }
+            let count = book.and_then(|book| book.verses(point.chapter));
+            if point.chapter == end.chapter || count.is_some_and(|count| point.verse < count) {
+                Some(VersePoint {
+                    chapter: point.chapter,
+                    verse: point.verse + 1,
+                })
+            } else {
+                Some(VersePoint {
+                    chapter: point.chapter + 1,
+                    verse: 1,
+                })
+            }
+        })
+    }
+}
+
+impl BibleReference {
+    /// Iterates over every verse of the reference in source order, expanding
+    /// segments only as they are reached. Whole books and open ranges are
+    /// expanded by the bundled verse counts. Of a range running into other books only the
+    /// first book is given, see `verse_ids` for all of them
+    pub fn iter_verses<'a>(&'a self) -> impl Iterator<Item = VersePoint> + 'a {
+        let book = self.book_id;
+        let segments: Vec<Segment> = if self.segments.is_empty() || self.book_end.is_some() {
+            self.book_segments()
+                .into_iter()
+                .filter(|&(first, _)| Some(first) == book)
+                .map(|(_, segment)| segment)
+                .collect()
+        } else {
+            self.resolved_segments()
+        };
+        segments
+            .into_iter()
+            .flat_map(move |segment| segment.iter_verses(book))
+    }
+}
+
+impl VerseLocation {
+    /// Compact segments of the location
+    pub fn segments(&self) -> Vec<Segment> {
+        if let Some(range) = self.range {
+            return vec![Segment::verses(range.start, range.end)];
+        }
+
+        match self.verses {
+            None => runs(&self.chapters)
+                .into_iter()
+                .map(|(first, last)| Segment::chapters(first, last))
+                .collect(),
+            Some(ref verses) => self
+                .chapters
+                .iter()
+                .flat_map(|&chapter| {
+                    runs(verses).into_iter().map(move |(first, last)| {
+                        Segment::verses(
+                            VersePoint {
+                                chapter,
+                                verse: first,
+                            },
+                            VersePoint {
+                                chapter,
+                                verse: last,
+                            },
+                        )
+                    })
+                }).collect(),
+        }
+    }
+}
+
+/// Groups segments into locations the way the parser does: consecutive
+/// chapters into one location, consecutive verses of the same chapter into
+/// another, each range crossing chapter boundary into its own
+pub(crate) fn group(segments: &[(Segment, Span)]) -> Vec<(VerseLocation, Span)> {
+    let mut locations: Vec<(VerseLocation, Span)> = Vec::new();
+    for &(segment, span) in segments {
+        let (chapter, verses) = match segment {
+            Segment::Chapter(chapter) => (None, vec![chapter]),
+            Segment::Chapters(first, last) => (None, (first..=last).collect()),
+            Segment::Verse(point) => (Some(point.chapter), vec![point.verse]),
+            Segment::Verses(range) if range.start.chapter == range.end.chapter => (
+                Some(range.start.chapter),
+                (range.start.verse..=range.end.verse).collect(),
+            ),
+            Segment::Verses(range) => {
+                let location = VerseLocation {
+                    chapters: (range.start.chapter..=range.end.chapter).collect(),
+                    verses: None,
+                    range: Some(range),
+                    parts: vec![],
+                    open_ranges: vec![],
+                };
+                locations.push((location, span));
+                continue;
+            }
+        };
+
+        if let Some(&mut (ref mut location, ref mut location_span)) = locations.last_mut() {
+            let extends = location.range.is_none()
+                && match chapter {
+                    Some(chapter) => location.verses.is_some() && location.chapters == [chapter],
+                    None => location.verses.is_none(),
+                };
+            if extends {
+                match location.verses {
+                    Some(ref mut list) => list.extend(verses),
+                    None => location.chapters.extend(verses),
+                }
+                location_span.end = span.end;
+                continue;
+            }
+        }
+        let location = match chapter {
+            Some(chapter) => VerseLocation {
+                chapters: vec![chapter],
+                verses: Some(verses),
+                range: None,
+                parts: vec![],
+                open_ranges: vec![],
+            },
+            None => VerseLocation {
+                chapters: verses,
+                verses: None,
+                range: None,
+                parts: vec![],
+                open_ranges: vec![],
+            },
+        };
+        locations.push((location, span));
+    }
+    locations
+}
+
+/// Splits numbers into runs of consecutive ones
+pub(crate) fn runs(numbers: &[u8]) -> Vec<(u8, u8)> {
+    let mut runs: Vec<(u8, u8)> = Vec::new();
+    for &number in numbers {
+        match runs.last_mut() {
+            Some(&mut (_, ref mut last)) if u16::from(*last) + 1 == u16::from(number) => {
+                *last = number
+            }
+            _ => runs.push((number, number)),
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use parse;
+
+    fn point(chapter: u8, verse: u8) -> VersePoint {
+        VersePoint { chapter, verse }
+    }
+
+    #[test]
+    fn test_runs() {
+        assert_eq!(runs(&[5, 6, 7, 8, 10]), [(5, 8), (10, 10)]);
+        assert_eq!(runs(&[3, 2]), [(3, 3), (2, 2)]);
+        assert_eq!(runs(&[]), []);
+    }
+
+    #[test]
+    fn test_segments() {
+        let r = parse("Ps 119:1-176").remove(0);
+        assert_eq!(
+            r.segments,
+            [Segment::Verses(VerseRange {
+                start: point(119, 1),
+                end: point(119, 176),
+            })]
+        );
+
+        let r = parse("Gen 1,2,3 5-6, 7:1, 3-4, 8:30-9:2").remove(0);
+        assert_eq!(
+            r.segments,
+            [
+                Segment::Chapter(1),
+                Segment::Chapter(2),
+                Segment::Chapter(3),
+                Segment::Chapters(5, 6),
+                Segment::Verse(point(7, 1)),
+                Segment::Verses(VerseRange {
+                    start: point(7, 3),
+                    end: point(7, 4),
+                }),
+                Segment::Verses(VerseRange {
+                    start: point(8, 30),
+                    end: point(9, 2),
+                }),
+            ]
+        );
+
+        let location = &r.locations[0];
+        assert_eq!(location.segments(), [Segment::Chapters(1, 3)]);
+    }
+
+    #[cfg(feature = "english")]
+    #[test]
+    fn test_iter_verses() {
+        let r = parse("Gen 1:30-2:2, 4").remove(0);
+        assert_eq!(
+            r.iter_verses().collect::<Vec<_>>(),
+            [
+                point(1, 30),
+                point(1, 31),
+                point(2, 1),
+                point(2, 2),
+                point(2, 4)
+            ]
+        );
+
+        let r = parse("Ruth 1").remove(0);
+        assert_eq!(r.iter_verses().count(), 22);
+
+        let r = parse("Ps 119").remove(0);
+        assert_eq!(r.iter_verses().last(), Some(point(119, 176)));
+
+        let r = parse("Notes 1, 2:3-4").remove(0);
+        assert_eq!(
+            r.iter_verses().collect::<Vec<_>>(),
+            [point(2, 3), point(2, 4)]
+        );
+    }
+}
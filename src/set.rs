@@ -0,0 +1,264 @@
+//! Sets of verses
+//!
+//! Set is kept as sorted inclusive ranges of verse IDs. Neighbouring ranges
+//! are merged using the bundled verse counts, so `Gen 1:31` and `Gen 2:1`
+//! become one range, but ranges never cross book boundaries. Verses cited
+//! in part (`2a`) count as whole verses, and open ranges (`8:28ff.`) run to
+//! their last verse.
+
+use std::cmp;
+
+use segment::{group, Segment};
+use {BibleReference, Span, Typography, VerseId};
+
+/// Set of verses with set algebra
+#[derive(Eq, PartialEq, Debug, Clone, Default)]
+pub struct VerseSet {
+    ranges: Vec<(VerseId, VerseId)>,
+}
+
+impl VerseSet {
+    /// Empty set
+    pub fn new() -> VerseSet {
+        VerseSet::default()
+    }
+
+    /// Set of verses of the references. References with unresolved books are skipped
+    pub fn from_references(references: &[BibleReference]) -> VerseSet {
+        let mut set = VerseSet::new();
+        for reference in references {
+            set.insert(reference);
+        }
+        set
+    }
+
+    /// Adds every verse of the reference
+    pub fn insert(&mut self, reference: &BibleReference) {
+        self.ranges.extend(reference.id_ranges());
+        self.normalize();
+    }
+
+    /// Sorted inclusive ranges of verse IDs, none of them adjacent
+    pub fn ranges(&self) -> &[(VerseId, VerseId)] {
+        &self.ranges
+    }
+
+    /// Whether the set has no verses
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Verses in either set
+    pub fn union(&self, other: &VerseSet) -> VerseSet {
+        let mut set = VerseSet {
+            ranges: self
+                .ranges
+                .iter()
+                .chain(other.ranges.iter())
+                .cloned()
+                .collect(),
+        };
+        set.normalize();
+        set
+    }
+
+    /// Verses in both sets
+    pub fn intersection(&self, other: &VerseSet) -> VerseSet {
+        let mut ranges = vec![];
+        let (mut left, mut right) = (0, 0);
+        while left < self.ranges.len() && right < other.ranges.len() {
+            let (a, b) = (self.ranges[left], other.ranges[right]);
+            let start = cmp::max(a.0, b.0);
+            let end = cmp::min(a.1, b.1);
+            if start <= end {
+                ranges.push((start, end));
+            }
+            if a.1 < b.1 {
+                left += 1;
+            } else {
+                right += 1;
+            }
+        }
+        VerseSet { ranges }
+    }
+
+    /// Verses of this set not in the other one
+    pub fn difference(&self, other: &VerseSet) -> VerseSet {
+        let mut ranges = vec![];
+        for &(start, end) in &self.ranges {
+            let mut start = Some(start);
+            for &(cut_start, cut_end) in &other.ranges {
+                let current = match start {
+                    Some(current) if cut_start <= end => current,
+                    _ => break,
+                };
+                if cut_end < current {
+                    continue;
+                }
+                if cut_start > current {
+                    if let Some(previous) = cut_start.previous() {
+                        ranges.push((current, previous));
+                    }
+                }
+                start = cut_end.next();
+            }
+            if let Some(start) = start {
+                if start <= end {
+                    ranges.push((start, end));
+                }
+            }
+        }
+        VerseSet { ranges }
+    }
+
+    /// Whether every verse of the other set is in this one
+    pub fn contains(&self, other: &VerseSet) -> bool {
+        other.difference(self).is_empty()
+    }
+
+    /// Whether the verse is in the set
+    pub fn contains_verse(&self, id: VerseId) -> bool {
+        self.ranges
+            .iter()
+            .any(|&(start, end)| start <= id && id <= end)
+    }
+
+    /// Whether the sets have at least one verse in common
+    pub fn overlaps(&self, other: &VerseSet) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// Minimal list of references, one per book, with whole chapters
+    /// written as chapters. References have no spans
+    pub fn to_references(&self) -> Vec<BibleReference> {
+        let mut references: Vec<BibleReference> = vec![];
+        for &(start, end) in &self.ranges {
+            let book = start.book();
+            let whole = start.point().verse == 1
+                && book.verses(end.point().chapter) == Some(end.point().verse);
+            let segment = if whole {
+                Segment::chapters(start.point().chapter, end.point().chapter)
+            } else {
+                Segment::verses(start.point(), end.point())
+            };
+
+            match references.last_mut() {
+                Some(ref mut reference) if reference.book_id == Some(book) => {
+                    reference.segments.push(segment)
+                }
+                _ => references.push(BibleReference {
+                    book: book.name().to_string(),
+                    book_id: Some(book),
+                    locations: vec![],
+                    segments: vec![segment],
+                    span: Span::default(),
+                    book_span: Span::default(),
+                    location_spans: vec![],
+                    inferred: false,
+                    typography: Typography::default(),
+                    book_end: None,
+                }),
+            }
+        }
+
+        for reference in &mut references {
+            let segments: Vec<(Segment, Span)> = reference
+                .segments
+                .iter()
+                .map(|&segment| (segment, Span::default()))
+                .collect();
+            reference.locations = group(&segments)
+                .into_iter()
+                .map(|(location, _)| location)
+                .collect();
+        }
+        references
+    }
+
+    /// Sorts ranges and merges overlapping and adjacent ones
+    fn normalize(&mut self) {
+        self.ranges.sort();
+        let mut ranges: Vec<(VerseId, VerseId)> = Vec::with_capacity(self.ranges.len());
+        for &(start, end) in &self.ranges {
+            if let Some(last) = ranges.last_mut() {
+                if start <= last.1 || last.1.next() == Some(start) {
+                    last.1 = cmp::max(last.1, end);
+                    continue;
+                }
+            }
+            ranges.push((start, end));
+        }
+        self.ranges = ranges;
+    }
+}
+
+impl From<&BibleReference> for VerseSet {
+    fn from(reference: &BibleReference) -> VerseSet {
+        let mut set = VerseSet::new();
+        set.insert(reference);
+        set
+    }
+}
+
+#[cfg(all(test, feature = "english"))]
+mod tests {
+
+    use super::*;
+    use {parse, Book, VersePoint};
+
+    fn set(text: &str) -> VerseSet {
+        VerseSet::from_references(&parse(text))
+    }
+
+    fn text(set: &VerseSet) -> String {
+        let references: Vec<String> = set
+            .to_references()
+            .iter()
+            .map(|reference| reference.to_string())
+            .collect();
+        references.join("; ")
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(text(&set("Gen 1:1-3, 4, 2-5")), "Ge 1:1-5");
+        assert_eq!(text(&set("Gen 1:30-31, 2:1-3")), "Ge 1:30-2:3");
+        assert_eq!(text(&set("Gen 1:1-31 2")), "Ge 1-2");
+        assert_eq!(text(&set("Gen 50 Exod 1")), "Ge 50; Ex 1");
+        assert_eq!(set("Gen 50 Exod 1").ranges().len(), 2);
+        assert!(set("Notes 1:1").is_empty());
+        assert_eq!(text(&set("Rom 8:28ff.")), "Ro 8:28-39");
+    }
+
+    #[test]
+    fn test_algebra() {
+        let a = set("Rom 8");
+        let b = set("Rom 8:28-9:5");
+        assert_eq!(text(&a.union(&b)), "Ro 8:1-9:5");
+        assert_eq!(text(&a.intersection(&b)), "Ro 8:28-39");
+        assert_eq!(text(&a.difference(&b)), "Ro 8:1-27");
+        assert_eq!(text(&b.difference(&a)), "Ro 9:1-5");
+        assert_eq!(
+            text(&a.difference(&set("Rom 8:3, 5-6"))),
+            "Ro 8:1-2, 4, 7-39"
+        );
+        assert!(a.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn test_contains_and_overlaps() {
+        let chapter = set("Rom 8");
+        assert!(chapter.contains(&set("Rom 8:28")));
+        assert!(chapter.contains(&set("Rom 8:1-39")));
+        assert!(!chapter.contains(&set("Rom 8:39-9:1")));
+        assert!(chapter.overlaps(&set("Rom 8:39-9:1")));
+        assert!(!chapter.overlaps(&set("Rom 9 Gen 8")));
+        assert!(chapter.contains_verse(VerseId::new(
+            Book::Romans,
+            VersePoint {
+                chapter: 8,
+                verse: 1
+            }
+        )));
+    }
+}
@@ -0,0 +1,75 @@
+//! Books with only one chapter
+//!
+//! Obadiah, Philemon, 2 and 3 John and Jude are cited by verse alone:
+//! `Jude 5` is verse 5, not chapter 5. The chapter may still be written,
+//! so `Jude 1:5` is the same verse.
+
+use std::mem;
+
+use {BibleReference, Book};
+
+/// Reads locations written without verses in a book with one chapter as
+/// verses of that chapter: `Jude 5`, `Phlm 8-10`
+pub(crate) fn read_as_verses(reference: &mut BibleReference) {
+    if !reference.book_id.is_some_and(Book::is_single_chapter) {
+        return;
+    }
+
+    let mut changed = false;
+    for location in &mut reference.locations {
+        if location.verses.is_none() && location.range.is_none() {
+            location.verses = Some(mem::replace(&mut location.chapters, vec![1]));
+            changed = true;
+        }
+    }
+    if changed {
+        reference.segments = reference
+            .locations
+            .iter()
+            .flat_map(|location| location.segments())
+            .collect();
+    }
+}
+
+#[cfg(all(test, feature = "english"))]
+mod tests {
+
+    use {parse, parse_with, BibleReference, ParseOptions, VerseSet};
+
+    #[test]
+    fn test_single_chapter_books() {
+        let refs = parse("Jude 5, Obad 3-4; Phlm 10. 2 John 1:12, Ruth 2");
+        let verses: Vec<(Vec<u8>, Option<Vec<u8>>)> = refs
+            .iter()
+            .flat_map(|reference| reference.locations.iter())
+            .map(|location| (location.chapters.clone(), location.verses.clone()))
+            .collect();
+        assert_eq!(
+            verses,
+            [
+                (vec![1], Some(vec![5])),
+                (vec![1], Some(vec![3, 4])),
+                (vec![1], Some(vec![10])),
+                (vec![1], Some(vec![12])),
+                (vec![2], None),
+            ]
+        );
+        assert!(refs.iter().all(|r| r.validate().is_ok()));
+        assert_eq!(refs[0].to_string(), "Jd 1:5");
+
+        let whole = VerseSet::from(&"Jude 1:1-25".parse::<BibleReference>().unwrap());
+        assert_eq!(whole.to_references()[0].to_string(), "Jd 1:1-25");
+        assert_eq!(
+            "Jude 5".parse::<BibleReference>().unwrap().locations[0].verses,
+            Some(vec![5])
+        );
+
+        let options = ParseOptions {
+            single_chapter_verses: false,
+            ..ParseOptions::default()
+        };
+        let refs = parse_with("Jude 5", &options);
+        assert_eq!(refs[0].locations[0].chapters, [5]);
+        assert_eq!(refs[0].locations[0].verses, None);
+    }
+}
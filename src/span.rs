@@ -0,0 +1,83 @@
+//! Positions of parsed references in the source text
+
+/// Byte range in the parsed text
+#[derive(Hash, Eq, PartialEq, Debug, Clone, Copy, Default)]
+pub struct Span {
+    /// Byte offset of the first character
+    pub start: usize,
+    /// Byte offset past the last character
+    pub end: usize,
+}
+
+/// Character position in the parsed text
+#[derive(Hash, Eq, PartialEq, Debug, Clone, Copy, Default)]
+pub struct Position {
+    /// Offset in characters from the start of the text
+    pub offset: usize,
+    /// Line number, starting from 1
+    pub line: usize,
+    /// Column in characters, starting from 1
+    pub column: usize,
+}
+
+impl Span {
+    /// Text covered by the span
+    pub fn slice<'a>(&self, text: &'a str) -> &'a str {
+        &text[self.start..self.end]
+    }
+
+    /// Character positions of the span start and end in the text it was parsed from
+    pub fn positions(&self, text: &str) -> (Position, Position) {
+        (position(text, self.start), position(text, self.end))
+    }
+
+    /// Moves the span by `offset` bytes
+    pub(crate) fn shift(self, offset: usize) -> Span {
+        Span {
+            start: self.start + offset,
+            end: self.end + offset,
+        }
+    }
+}
+
+/// Converts byte offset into character position
+fn position(text: &str, offset: usize) -> Position {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    Position {
+        offset: before.chars().count(),
+        line: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count() + 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_positions() {
+        let text = "Быт 1\nИсх 2:1";
+        let span = Span { start: 9, end: 19 };
+        assert_eq!(span.slice(text), "Исх 2:1");
+
+        let (start, end) = span.positions(text);
+        assert_eq!(
+            start,
+            Position {
+                offset: 6,
+                line: 2,
+                column: 1
+            }
+        );
+        assert_eq!(
+            end,
+            Position {
+                offset: 13,
+                line: 2,
+                column: 8
+            }
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! Unicode forms of punctuation, spaces and digits
+//!
+//! Text pasted from word processors and typeset books uses typographic
+//! dashes (`1:1–3`), no-break and thin spaces, and East Asian text uses
+//! full-width digits and punctuation (`３：１６`). All of them are read as
+//! their ASCII counterparts, and `Typography` records which were written.
+
+use std::char;
+
+use regex;
+
+/// Characters read as the range dash: hyphen-minus, hyphen, non-breaking
+/// hyphen, figure dash, en dash, em dash, horizontal bar, minus sign,
+/// small and full-width hyphen-minus
+pub(crate) const DASHES: &[char] = &[
+    '-', '\u{2010}', '\u{2011}', '\u{2012}', '\u{2013}', '\u{2014}', '\u{2015}', '\u{2212}',
+    '\u{FE63}', '\u{FF0D}',
+];
+
+/// Offset of full-width forms of printable ASCII (U+FF01..U+FF5E)
+const FULL_WIDTH_OFFSET: u32 = 0xFEE0;
+
+/// Non-ASCII forms a reference was written with
+#[derive(Hash, Eq, PartialEq, Debug, Clone, Copy, Default)]
+pub struct Typography {
+    /// Dash of the first range, `None` without ranges
+    pub dash: Option<char>,
+    /// First non-ASCII space (no-break, thin, ideographic). Tabs and line
+    /// breaks are not recorded
+    pub space: Option<char>,
+    /// Whether full-width digits or punctuation were used
+    pub full_width: bool,
+}
+
+impl Typography {
+    /// Forms used in the text of a reference
+    pub(crate) fn of(text: &str) -> Typography {
+        let mut typography = Typography::default();
+        for c in text.chars() {
+            if typography.dash.is_none() && DASHES.contains(&c) {
+                typography.dash = Some(c);
+            }
+            if typography.space.is_none() && is_unicode_space(c) {
+                typography.space = Some(c);
+            }
+            typography.full_width |= full_width_ascii(c).is_some();
+        }
+        typography
+    }
+}
+
+/// ASCII character of a full-width form
+fn full_width_ascii(c: char) -> Option<char> {
+    match c {
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - FULL_WIDTH_OFFSET),
+        _ => None,
+    }
+}
+
+/// Reads typographic dashes as `-` and full-width forms as ASCII
+pub(crate) fn fold(c: char) -> char {
+    if DASHES.contains(&c) {
+        '-'
+    } else {
+        full_width_ascii(c).unwrap_or(c)
+    }
+}
+
+/// Whether the character is a space separator (`\pZ`) outside of ASCII
+fn is_unicode_space(c: char) -> bool {
+    !c.is_ascii() && c.is_whitespace() && !c.is_control()
+}
+
+/// Pattern of a character, matching its full-width form as well
+pub(crate) fn pattern(c: char) -> String {
+    let mut pattern = regex::escape(&c.to_string());
+    if ('\u{21}'..='\u{7E}').contains(&c) {
+        if let Some(full) = char::from_u32(c as u32 + FULL_WIDTH_OFFSET) {
+            pattern.push(full);
+        }
+    }
+    pattern
+}
+
+/// Character class of the range dashes
+pub(crate) fn dash_pattern() -> String {
+    let dashes: Vec<String> = DASHES
+        .iter()
+        .map(|&c| regex::escape(&c.to_string()))
+        .collect();
+    format!("[{}]", dashes.concat())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    #[cfg(feature = "english")]
+    use {parse, BibleReference, Book};
+
+    #[test]
+    fn test_fold() {
+        assert_eq!(fold('–'), '-');
+        assert_eq!(fold('−'), '-');
+        assert_eq!(fold('：'), ':');
+        assert_eq!(fold('１'), '1');
+        assert_eq!(fold('a'), 'a');
+    }
+
+    #[cfg(feature = "english")]
+    #[test]
+    fn test_parse_unicode_forms() {
+        let refs = parse("Gen 1:1–3 and Rom 8:28‒30");
+        assert_eq!(refs[0].locations[0].verses, Some(vec![1, 2, 3]));
+        assert_eq!(refs[0].typography.dash, Some('–'));
+        assert_eq!(refs[1].locations[0].verses, Some(vec![28, 29, 30]));
+
+        let refs = parse("Ps\u{a0}23:1\u{2212}3");
+        assert_eq!(refs[0].book_id, Some(Book::Psalms));
+        assert_eq!(refs[0].locations[0].verses, Some(vec![1, 2, 3]));
+        assert_eq!(
+            refs[0].typography,
+            Typography {
+                dash: Some('\u{2212}'),
+                space: Some('\u{a0}'),
+                full_width: false,
+            }
+        );
+
+        let text = "John ３：１６，１８";
+        let refs = parse(text);
+        assert_eq!(refs[0].locations[0].chapters, [3]);
+        assert_eq!(refs[0].locations[0].verses, Some(vec![16, 18]));
+        assert!(refs[0].typography.full_width);
+        assert_eq!(refs[0].span.slice(text), text);
+
+        let refs = parse("Gen 1:1-3");
+        assert_eq!(refs[0].typography.dash, Some('-'));
+        assert!(!refs[0].typography.full_width);
+
+        let text = "Gen 1:1,\n3 and Ps\t23:1,\u{2009}2";
+        let refs = parse(text);
+        assert_eq!(refs[0].span.slice(text), "Gen 1:1,\n3");
+        assert_eq!(refs[0].typography.space, None);
+        assert_eq!(refs[1].typography.space, Some('\u{2009}'));
+    }
+
+    #[cfg(feature = "english")]
+    #[test]
+    fn test_parse_spaced_dashes() {
+        let text = "Gen 1:1 \u{2013} 3 and Rom 8:28 \u{2012} 30, Ps 1 \u{2013} 3";
+        let refs = parse(text);
+        assert_eq!(refs.len(), 3);
+        assert_eq!(refs[0].locations[0].verses, Some(vec![1, 2, 3]));
+        assert_eq!(refs[0].span.slice(text), "Gen 1:1 \u{2013} 3");
+        assert_eq!(refs[0].typography.dash, Some('\u{2013}'));
+        assert_eq!(refs[1].locations[0].verses, Some(vec![28, 29, 30]));
+        assert_eq!(refs[1].typography.dash, Some('\u{2012}'));
+        assert_eq!(refs[2].locations[0].chapters, [1, 2, 3]);
+        assert_eq!(
+            "Gen 1:30 \u{2014} 2:3".parse::<BibleReference>().unwrap().segments,
+            parse("Gen 1:30-2:3")[0].segments
+        );
+    }
+}
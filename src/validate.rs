@@ -0,0 +1,319 @@
+//! Validation of references against chapter and verse bounds
+
+use {BibleReference, Book, Segment, ValidationError, VerseLocation, VersePoint, VerseRange};
+
+impl BibleReference {
+    /// Checks every location against the bounds of the resolved book, and
+    /// the end of a range across books against the bounds of its last book
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let book = self.book_id.ok_or(ValidationError::UnknownBook)?;
+        self.locations
+            .iter()
+            .try_for_each(|location| location.validate(book))?;
+
+        let end = match self.book_end {
+            Some(ref end) => end,
+            None => return Ok(()),
+        };
+        let book = end.book_id.ok_or(ValidationError::UnknownBook)?;
+        match (end.chapter, end.verse) {
+            (Some(chapter), Some(verse)) => validate_point(book, VersePoint { chapter, verse }),
+            (Some(chapter), None) => book
+                .verses(chapter)
+                .map(|_| ())
+                .ok_or(ValidationError::ChapterOutOfBounds { book, chapter }),
+            (None, _) => Ok(()),
+        }
+    }
+
+    /// Drops chapters and verses outside of the resolved book and shortens
+    /// ranges running past its end. Locations left empty are removed together
+    /// with their spans. Does nothing when the book is not resolved.
+    ///
+    /// Returns `false` and leaves the reference unchanged when none of its
+    /// locations is within the book, since a reference without locations
+    /// would cite the whole book
+    pub fn clamp_to_book(&mut self) -> bool {
+        let book = match self.book_id {
+            Some(book) => book,
+            None => return true,
+        };
+
+        let mut locations = self.locations.clone();
+        let keep: Vec<bool> = locations
+            .iter_mut()
+            .map(|location| location.clamp(book))
+            .collect();
+        if !self.locations.is_empty() && !keep.contains(&true) {
+            return false;
+        }
+
+        self.locations = locations;
+        let mut flags = keep.iter();
+        self.locations
+            .retain(|_| flags.next().cloned().unwrap_or(true));
+        let mut flags = keep.iter();
+        self.location_spans
+            .retain(|_| flags.next().cloned().unwrap_or(true));
+        self.segments = self
+            .segments
+            .iter()
+            .filter_map(|segment| segment.clamp(book))
+            .collect();
+        true
+    }
+}
+
+impl Segment {
+    /// Drops chapters and verses outside of the book and shortens the range
+    /// running past its end. Returns `None` when nothing is left
+    pub fn clamp(self, book: Book) -> Option<Segment> {
+        match self {
+            Segment::Chapter(chapter) => book.verses(chapter).map(|_| self),
+            Segment::Chapters(first, last) => {
+                let first = first.max(1);
+                let last = last.min(book.chapters());
+                if first <= last {
+                    Some(Segment::chapters(first, last))
+                } else {
+                    None
+                }
+            }
+            Segment::Verse(point) => validate_point(book, point).ok().map(|_| self),
+            Segment::Verses(range) => {
+                clamp_range(book, range).map(|range| Segment::verses(range.start, range.end))
+            }
+        }
+    }
+}
+
+impl VerseLocation {
+    /// Checks that every chapter and verse exists in the book
+    pub fn validate(&self, book: Book) -> Result<(), ValidationError> {
+        if let Some(range) = self.range {
+            validate_point(book, range.start)?;
+            return validate_point(book, range.end);
+        }
+
+        for &chapter in &self.chapters {
+            let count = book
+                .verses(chapter)
+                .ok_or(ValidationError::ChapterOutOfBounds { book, chapter })?;
+            for &verse in self.verses.iter().flat_map(|verses| verses.iter()) {
+                if verse == 0 || verse > count {
+                    return Err(ValidationError::VerseOutOfBounds {
+                        book,
+                        chapter,
+                        verse,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Drops chapters and verses outside of the book and shortens the range
+    /// running past its end, together with the parts and open ranges of the
+    /// dropped verses. Returns `false` when nothing is left
+    pub fn clamp(&mut self, book: Book) -> bool {
+        let kept = self.clamp_verses(book);
+        let range = self.range;
+        let chapters = &self.chapters;
+        let verses = &self.verses;
+        let cited = |point: VersePoint| match range {
+            Some(range) => range.start <= point && point <= range.end,
+            None => {
+                chapters.contains(&point.chapter)
+                    && verses
+                        .as_ref()
+                        .is_some_and(|verses| verses.contains(&point.verse))
+            }
+        };
+        self.parts.retain(|part| cited(part.point));
+        self.open_ranges.retain(|open| cited(open.start));
+        kept
+    }
+
+    /// Clamps chapters, verses and the range, see `clamp`
+    fn clamp_verses(&mut self, book: Book) -> bool {
+        if let Some(range) = self.range {
+            self.range = clamp_range(book, range);
+            self.chapters = match self.range {
+                Some(range) => (range.start.chapter..=range.end.chapter).collect(),
+                None => vec![],
+            };
+            return self.range.is_some();
+        }
+
+        self.chapters
+            .retain(|&chapter| book.verses(chapter).is_some());
+        let count = self
+            .chapters
+            .first()
+            .and_then(|&chapter| book.verses(chapter))
+            .unwrap_or(0);
+        if let Some(ref mut verses) = self.verses {
+            verses.retain(|&verse| verse >= 1 && verse <= count);
+            if verses.is_empty() {
+                return false;
+            }
+        }
+        !self.chapters.is_empty()
+    }
+}
+
+fn validate_point(book: Book, point: VersePoint) -> Result<(), ValidationError> {
+    let count = book
+        .verses(point.chapter)
+        .ok_or(ValidationError::ChapterOutOfBounds {
+            book,
+            chapter: point.chapter,
+        })?;
+    if point.verse == 0 || point.verse > count {
+        return Err(ValidationError::VerseOutOfBounds {
+            book,
+            chapter: point.chapter,
+            verse: point.verse,
+        });
+    }
+    Ok(())
+}
+
+/// Moves range start to the first existing verse and range end to the last one
+fn clamp_range(book: Book, range: VerseRange) -> Option<VerseRange> {
+    let mut start = range.start;
+    let mut end = range.end;
+
+    if start.chapter == 0 {
+        start = VersePoint {
+            chapter: 1,
+            verse: 1,
+        };
+    }
+    start.verse = start.verse.max(1);
+    if start.verse > book.verses(start.chapter)? {
+        start = VersePoint {
+            chapter: start.chapter.checked_add(1)?,
+            verse: 1,
+        };
+        book.verses(start.chapter)?;
+    }
+
+    if end.chapter > book.chapters() {
+        end.chapter = book.chapters();
+        end.verse = u8::MAX;
+    }
+    end.verse = end.verse.min(book.verses(end.chapter)?);
+
+    if start <= end {
+        Some(VerseRange { start, end })
+    } else {
+        None
+    }
+}
+
+#[cfg(all(test, feature = "english"))]
+mod tests {
+
+    use super::*;
+    use {parse, VersePart};
+
+    #[test]
+    fn test_validate() {
+        let refs = parse("John 3:16; Jude 2:3; Ps 151; Gen 1:32; Gen 50:26-51:2; Foo 1");
+        assert_eq!(refs[0].validate(), Ok(()));
+        assert_eq!(
+            refs[1].validate(),
+            Err(ValidationError::ChapterOutOfBounds {
+                book: Book::Jude,
+                chapter: 2,
+            })
+        );
+        assert_eq!(
+            refs[2].validate(),
+            Err(ValidationError::ChapterOutOfBounds {
+                book: Book::Psalms,
+                chapter: 151,
+            })
+        );
+        assert_eq!(
+            refs[3].validate(),
+            Err(ValidationError::VerseOutOfBounds {
+                book: Book::Genesis,
+                chapter: 1,
+                verse: 32,
+            })
+        );
+        assert_eq!(
+            refs[4].validate(),
+            Err(ValidationError::ChapterOutOfBounds {
+                book: Book::Genesis,
+                chapter: 51,
+            })
+        );
+        assert_eq!(refs[5].validate(), Err(ValidationError::UnknownBook));
+    }
+
+    #[test]
+    fn test_clamp() {
+        let mut r = parse("Gen 1:30-35, 50-52").remove(0);
+        assert!(r.clamp_to_book());
+        assert_eq!(r.validate(), Ok(()));
+        assert_eq!(r.locations.len(), 1);
+        assert_eq!(r.locations[0].verses, Some(vec![30, 31]));
+        assert_eq!(r.location_spans.len(), 1);
+        assert_eq!(
+            r.segments,
+            [Segment::Verses(VerseRange {
+                start: VersePoint {
+                    chapter: 1,
+                    verse: 30
+                },
+                end: VersePoint {
+                    chapter: 1,
+                    verse: 31
+                },
+            })]
+        );
+
+        let mut r = parse("Gen 49:30-52:1").remove(0);
+        assert!(r.clamp_to_book());
+        let range = r.locations[0].range.unwrap();
+        assert_eq!(
+            range.end,
+            VersePoint {
+                chapter: 50,
+                verse: 26
+            }
+        );
+        assert_eq!(r.locations[0].chapters, [49, 50]);
+
+        let mut r = parse("Gen 1:2a, 30ff., 33b, 40ff.").remove(0);
+        assert!(r.clamp_to_book());
+        assert_eq!(r.locations[0].verses, Some(vec![2, 30]));
+        assert_eq!(
+            r.locations[0].parts,
+            [VersePart {
+                point: VersePoint {
+                    chapter: 1,
+                    verse: 2
+                },
+                part: 'a',
+            }]
+        );
+        assert_eq!(r.locations[0].open_ranges.len(), 1);
+        assert_eq!(r.locations[0].open_ranges[0].start.verse, 30);
+        assert_eq!(r.to_string(), "Ge 1:2a, 30ff.");
+
+        let mut r = parse("Gen 1:30-35b").remove(0);
+        assert!(r.clamp_to_book());
+        assert!(r.locations[0].parts.is_empty());
+
+        let mut r = parse("Jude 2:3-4").remove(0);
+        assert!(!r.clamp_to_book());
+        assert_eq!(r.locations.len(), 1);
+        assert!(!r.is_whole_book());
+        assert!(r.validate().is_err());
+        assert_eq!(r.to_string(), "Jd 2:3-4");
+    }
+}
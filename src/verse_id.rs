@@ -0,0 +1,278 @@
+//! Integer verse keys
+//!
+//! `VerseId` packs book, chapter and verse into one number that sorts in
+//! canonical order and never changes. Canon index numbers only existing
+//! verses from 0, so it depends on the versification.
+
+use std::collections::HashMap;
+
+use {BibleReference, Book, VersePoint, Versification, BOOKS};
+
+/// Packed verse identifier `BBCCCVVV`: book position in the canon (from 1)
+/// times 1 000 000, plus chapter times 1 000, plus verse
+#[derive(Hash, Eq, PartialEq, Ord, PartialOrd, Debug, Clone, Copy)]
+pub struct VerseId(u32);
+
+impl VerseId {
+    /// Packs verse of the book
+    pub fn new(book: Book, point: VersePoint) -> VerseId {
+        VerseId(
+            (book.index() as u32 + 1) * 1_000_000
+                + u32::from(point.chapter) * 1_000
+                + u32::from(point.verse),
+        )
+    }
+
+    /// Unpacks integer key, `None` unless it names a book, chapter and verse
+    pub fn from_u32(value: u32) -> Option<VerseId> {
+        let book = value / 1_000_000;
+        let chapter = value / 1_000 % 1_000;
+        let verse = value % 1_000;
+        let valid = (1..=BOOKS.len() as u32).contains(&book)
+            && (1..=255).contains(&chapter)
+            && (1..=255).contains(&verse);
+        if valid {
+            Some(VerseId(value))
+        } else {
+            None
+        }
+    }
+
+    /// Integer key
+    pub fn value(self) -> u32 {
+        self.0
+    }
+
+    /// Book
+    pub fn book(self) -> Book {
+        BOOKS[(self.0 / 1_000_000) as usize - 1]
+    }
+
+    /// Chapter and verse
+    pub fn point(self) -> VersePoint {
+        VersePoint {
+            chapter: (self.0 / 1_000 % 1_000) as u8,
+            verse: (self.0 % 1_000) as u8,
+        }
+    }
+
+    /// Next verse of the same book by the bundled verse counts,
+    /// `None` after the last verse
+    pub fn next(self) -> Option<VerseId> {
+        let (book, point) = (self.book(), self.point());
+        let count = book.verses(point.chapter).unwrap_or(0);
+        let point = if point.verse < count {
+            VersePoint {
+                chapter: point.chapter,
+                verse: point.verse + 1,
+            }
+        } else if point.chapter < book.chapters() {
+            VersePoint {
+                chapter: point.chapter + 1,
+                verse: 1,
+            }
+        } else {
+            return None;
+        };
+        Some(VerseId::new(book, point))
+    }
+
+    /// Previous verse of the same book by the bundled verse counts,
+    /// `None` before the first verse
+    pub fn previous(self) -> Option<VerseId> {
+        let (book, point) = (self.book(), self.point());
+        let point = if point.verse > 1 {
+            VersePoint {
+                chapter: point.chapter,
+                verse: point.verse - 1,
+            }
+        } else if point.chapter > 1 {
+            VersePoint {
+                chapter: point.chapter - 1,
+                verse: book.verses(point.chapter - 1).unwrap_or(1),
+            }
+        } else {
+            return None;
+        };
+        Some(VerseId::new(book, point))
+    }
+
+    /// Position of the verse among all verses of the canon, from 0.
+    /// Returns `None` when there is no such verse in the versification
+    pub fn index(self, versification: Versification) -> Option<u32> {
+        let point = self.point();
+        let count = versification.verses(self.book(), point.chapter)?;
+        if point.verse > count {
+            return None;
+        }
+        let chapters = &offsets(versification).0[self.book().index()];
+        Some(chapters[point.chapter as usize - 1] + u32::from(point.verse) - 1)
+    }
+
+    /// Verse at the position in the canon, `None` past the last verse
+    pub fn from_index(index: u32, versification: Versification) -> Option<VerseId> {
+        let (_, chapters) = offsets(versification);
+        let position = match chapters.binary_search_by_key(&index, |&(start, _, _)| start) {
+            Ok(position) => position,
+            Err(0) => return None,
+            Err(position) => position - 1,
+        };
+        let (start, book, chapter) = chapters[position];
+        let verse = index - start + 1;
+        if verse > u32::from(versification.verses(book, chapter)?) {
+            return None;
+        }
+        Some(VerseId::new(
+            book,
+            VersePoint {
+                chapter,
+                verse: verse as u8,
+            },
+        ))
+    }
+}
+
+impl From<VerseId> for u32 {
+    fn from(id: VerseId) -> u32 {
+        id.0
+    }
+}
+
+impl BibleReference {
+    /// Iterates over verse IDs of the reference in source order, through
+    /// every book it covers. Empty if a book is not resolved
+    pub fn verse_ids<'a>(&'a self) -> impl Iterator<Item = VerseId> + 'a {
+        self.book_segments()
+            .into_iter()
+            .flat_map(|(book, segment)| {
+                segment
+                    .iter_verses(Some(book))
+                    .map(move |point| VerseId::new(book, point))
+            })
+    }
+
+    /// Iterates over inclusive ranges of verse IDs, one for every segment
+    /// and every book a range runs through, ready for `BETWEEN` queries.
+    /// Empty if a book is not resolved
+    pub fn id_ranges<'a>(&'a self) -> impl Iterator<Item = (VerseId, VerseId)> + 'a {
+        self.book_segments()
+            .into_iter()
+            .filter_map(|(book, segment)| {
+                let (start, end) = segment.bounds(Some(book))?;
+                Some((VerseId::new(book, start), VerseId::new(book, end)))
+            })
+    }
+}
+
+/// Index of the first verse of every chapter, by book, and the same
+/// flattened in canonical order for lookup by index
+type Offsets = (Vec<Vec<u32>>, Vec<(u32, Book, u8)>);
+
+fn offsets(versification: Versification) -> &'static Offsets {
+    lazy_static! {
+        static ref OFFSETS: HashMap<Versification, Offsets> = [
+            Versification::Kjv,
+            Versification::Hebrew,
+            Versification::Septuagint,
+            Versification::Vulgate,
+            Versification::Synodal,
+        ]
+        .iter()
+        .map(|&versification| {
+            let mut total = 0;
+            let mut books = vec![];
+            let mut chapters = vec![];
+            for &book in BOOKS.iter() {
+                let mut starts = vec![];
+                for chapter in 1..=versification.chapters(book) {
+                    starts.push(total);
+                    chapters.push((total, book, chapter));
+                    total += u32::from(versification.verses(book, chapter).unwrap_or(0));
+                }
+                books.push(starts);
+            }
+            (versification, (books, chapters))
+        }).collect();
+    }
+
+    &OFFSETS[&versification]
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    #[cfg(feature = "english")]
+    use parse;
+
+    fn point(chapter: u8, verse: u8) -> VersePoint {
+        VersePoint { chapter, verse }
+    }
+
+    #[test]
+    fn test_packing() {
+        let id = VerseId::new(Book::John, point(3, 16));
+        assert_eq!(id.value(), 43_003_016);
+        assert_eq!(u32::from(id), 43_003_016);
+        assert_eq!(id.book(), Book::John);
+        assert_eq!(id.point(), point(3, 16));
+        assert_eq!(VerseId::from_u32(43_003_016), Some(id));
+        assert_eq!(VerseId::from_u32(67_001_001), None);
+        assert_eq!(VerseId::from_u32(1_000_001), None);
+        assert_eq!(VerseId::from_u32(1_001_000), None);
+        assert!(
+            VerseId::new(Book::Genesis, point(50, 26)) < VerseId::new(Book::Exodus, point(1, 1))
+        );
+    }
+
+    #[test]
+    fn test_index() {
+        let kjv = Versification::Kjv;
+        let first = VerseId::new(Book::Genesis, point(1, 1));
+        let last = VerseId::new(Book::Revelation, point(22, 21));
+        assert_eq!(first.index(kjv), Some(0));
+        assert_eq!(last.index(kjv), Some(31_101));
+        assert_eq!(
+            VerseId::new(Book::Exodus, point(1, 1)).index(kjv),
+            Some(1_533)
+        );
+        assert_eq!(VerseId::new(Book::Genesis, point(1, 32)).index(kjv), None);
+
+        assert_eq!(VerseId::from_index(0, kjv), Some(first));
+        assert_eq!(VerseId::from_index(31_101, kjv), Some(last));
+        assert_eq!(VerseId::from_index(31_102, kjv), None);
+        for &index in &[1, 1_532, 1_533, 23_145, 31_000] {
+            let id = VerseId::from_index(index, kjv).unwrap();
+            assert_eq!(id.index(kjv), Some(index));
+        }
+
+        let id = VerseId::new(Book::Genesis, point(1, 31));
+        assert_eq!(id.next().map(|id| id.point()), Some(point(2, 1)));
+        assert_eq!(id.next().and_then(|id| id.previous()), Some(id));
+        assert_eq!(first.previous(), None);
+        assert_eq!(VerseId::new(Book::Genesis, point(50, 26)).next(), None);
+
+        let malachi = VerseId::new(Book::Malachi, point(4, 1));
+        assert!(malachi.index(kjv).is_some());
+        assert_eq!(malachi.index(Versification::Hebrew), None);
+    }
+
+    #[cfg(feature = "english")]
+    #[test]
+    fn test_id_ranges() {
+        let r = parse("Gen 1:30-2:3, 5 Ps 119").remove(0);
+        let ranges: Vec<(u32, u32)> = r
+            .id_ranges()
+            .map(|(start, end)| (start.value(), end.value()))
+            .collect();
+        assert_eq!(ranges, [(1_001_030, 1_002_003), (1_002_005, 1_002_005)]);
+
+        let r = parse("Ps 119").remove(0);
+        let ranges: Vec<(VerseId, VerseId)> = r.id_ranges().collect();
+        assert_eq!(ranges[0].1.value(), 19_119_176);
+        assert_eq!(r.verse_ids().count(), 176);
+
+        let r = parse("Notes 1:1").remove(0);
+        assert_eq!(r.id_ranges().count(), 0);
+    }
+}
@@ -0,0 +1,694 @@
+//! Versification schemes and conversion between them
+//!
+//! Every scheme is described by the runs of verses it numbers differently
+//! from the King James Version, so conversion always goes through the KJV.
+//! A run maps verses one to one, or one verse to several (a Psalm title
+//! counted as separate verses, a verse split across chapters) and back.
+//!
+//! Bundled tables cover the Psalms in every scheme, the chapter boundary
+//! differences of the Hebrew text (Joel 3 – 4, Malachi 4, Exodus 8 and
+//! others) and a few well known differences of the Septuagint and the
+//! Synodal translation. Verses not listed keep their KJV numbers.
+
+use std::collections::HashMap;
+
+use data::VERSE_COUNTS;
+use {BibleReference, Book, VerseLocation, VersePoint, BOOKS};
+
+/// Chapter and verse numbering scheme
+#[derive(Hash, Eq, PartialEq, Debug, Clone, Copy)]
+pub enum Versification {
+    /// King James Version and most English translations
+    Kjv,
+    /// Hebrew Masoretic Text
+    Hebrew,
+    /// Greek Septuagint
+    Septuagint,
+    /// Latin Vulgate
+    Vulgate,
+    /// Russian Synodal translation
+    Synodal,
+}
+
+/// Verses `first..=last` of the chapter
+#[derive(Debug, Clone, Copy)]
+struct Verses {
+    chapter: u8,
+    first: u8,
+    last: u8,
+}
+
+/// Run of KJV verses numbered differently in another scheme
+#[derive(Debug, Clone, Copy)]
+struct Mapping {
+    book: Book,
+    kjv: Verses,
+    other: Verses,
+}
+
+// Book, KJV chapter, first and last verse, Hebrew chapter and first verse
+#[rustfmt::skip]
+static HEBREW_SHIFTS: &[(Book, u8, u8, u8, u8, u8)] = &[
+    (Book::Genesis, 31, 55, 55, 32, 1),
+    (Book::Genesis, 32, 1, 32, 32, 2),
+    (Book::Exodus, 8, 1, 4, 7, 26),
+    (Book::Exodus, 8, 5, 32, 8, 1),
+    (Book::Exodus, 22, 1, 1, 21, 37),
+    (Book::Exodus, 22, 2, 31, 22, 1),
+    (Book::Leviticus, 6, 1, 7, 5, 20),
+    (Book::Leviticus, 6, 8, 30, 6, 1),
+    (Book::Numbers, 16, 36, 50, 17, 1),
+    (Book::Numbers, 17, 1, 13, 17, 16),
+    (Book::Numbers, 29, 40, 40, 30, 1),
+    (Book::Numbers, 30, 1, 16, 30, 2),
+    (Book::Deuteronomy, 12, 32, 32, 13, 1),
+    (Book::Deuteronomy, 13, 1, 18, 13, 2),
+    (Book::Deuteronomy, 22, 30, 30, 23, 1),
+    (Book::Deuteronomy, 23, 1, 25, 23, 2),
+    (Book::Deuteronomy, 29, 1, 1, 28, 69),
+    (Book::Deuteronomy, 29, 2, 29, 29, 1),
+    (Book::FirstSamuel, 20, 42, 42, 21, 1),
+    (Book::FirstSamuel, 21, 1, 15, 21, 2),
+    (Book::FirstSamuel, 23, 29, 29, 24, 1),
+    (Book::FirstSamuel, 24, 1, 22, 24, 2),
+    (Book::SecondSamuel, 18, 33, 33, 19, 1),
+    (Book::SecondSamuel, 19, 1, 43, 19, 2),
+    (Book::FirstKings, 4, 21, 34, 5, 1),
+    (Book::FirstKings, 5, 1, 18, 5, 15),
+    (Book::FirstKings, 22, 43, 43, 22, 44),
+    (Book::FirstKings, 22, 44, 53, 22, 45),
+    (Book::SecondKings, 11, 21, 21, 12, 1),
+    (Book::SecondKings, 12, 1, 21, 12, 2),
+    (Book::FirstChronicles, 6, 1, 15, 5, 27),
+    (Book::FirstChronicles, 6, 16, 81, 6, 1),
+    (Book::FirstChronicles, 12, 4, 4, 12, 5),
+    (Book::FirstChronicles, 12, 5, 40, 12, 6),
+    (Book::SecondChronicles, 2, 1, 1, 1, 18),
+    (Book::SecondChronicles, 2, 2, 18, 2, 1),
+    (Book::SecondChronicles, 14, 1, 1, 13, 23),
+    (Book::SecondChronicles, 14, 2, 15, 14, 1),
+    (Book::Nehemiah, 4, 1, 6, 3, 33),
+    (Book::Nehemiah, 4, 7, 23, 4, 1),
+    (Book::Nehemiah, 9, 38, 38, 10, 1),
+    (Book::Nehemiah, 10, 1, 39, 10, 2),
+    (Book::Job, 41, 1, 8, 40, 25),
+    (Book::Job, 41, 9, 34, 41, 1),
+    (Book::Ecclesiastes, 5, 1, 1, 4, 17),
+    (Book::Ecclesiastes, 5, 2, 20, 5, 1),
+    (Book::SongOfSongs, 6, 13, 13, 7, 1),
+    (Book::SongOfSongs, 7, 1, 13, 7, 2),
+    (Book::Isaiah, 9, 1, 1, 8, 23),
+    (Book::Isaiah, 9, 2, 21, 9, 1),
+    (Book::Isaiah, 64, 1, 1, 63, 19),
+    (Book::Isaiah, 64, 2, 12, 64, 1),
+    (Book::Jeremiah, 9, 1, 1, 8, 23),
+    (Book::Jeremiah, 9, 2, 26, 9, 1),
+    (Book::Ezekiel, 20, 45, 49, 21, 1),
+    (Book::Ezekiel, 21, 1, 32, 21, 6),
+    (Book::Daniel, 4, 1, 3, 3, 31),
+    (Book::Daniel, 4, 4, 37, 4, 1),
+    (Book::Daniel, 5, 31, 31, 6, 1),
+    (Book::Daniel, 6, 1, 28, 6, 2),
+    (Book::Hosea, 1, 10, 11, 2, 1),
+    (Book::Hosea, 2, 1, 23, 2, 3),
+    (Book::Hosea, 11, 12, 12, 12, 1),
+    (Book::Hosea, 12, 1, 14, 12, 2),
+    (Book::Hosea, 13, 16, 16, 14, 1),
+    (Book::Hosea, 14, 1, 9, 14, 2),
+    (Book::Joel, 2, 28, 32, 3, 1),
+    (Book::Joel, 3, 1, 21, 4, 1),
+    (Book::Jonah, 1, 17, 17, 2, 1),
+    (Book::Jonah, 2, 1, 10, 2, 2),
+    (Book::Micah, 5, 1, 1, 4, 14),
+    (Book::Micah, 5, 2, 15, 5, 1),
+    (Book::Nahum, 1, 15, 15, 2, 1),
+    (Book::Nahum, 2, 1, 13, 2, 2),
+    (Book::Zechariah, 1, 18, 21, 2, 1),
+    (Book::Zechariah, 2, 1, 13, 2, 5),
+    (Book::Malachi, 4, 1, 6, 3, 19),
+];
+
+// Verses split between two chapters in the Hebrew text, in addition to the shifts
+#[rustfmt::skip]
+static HEBREW_SPLITS: &[(Book, u8, u8, u8, u8)] = &[
+    (Book::FirstSamuel, 20, 42, 20, 42),
+    (Book::FirstKings, 22, 43, 22, 43),
+    (Book::FirstChronicles, 12, 4, 12, 4),
+];
+
+// Book, KJV chapter, first and last verse, Septuagint chapter and first verse
+#[rustfmt::skip]
+static SEPTUAGINT_SHIFTS: &[(Book, u8, u8, u8, u8, u8)] = &[
+    (Book::Joel, 2, 28, 32, 3, 1),
+    (Book::Joel, 3, 1, 21, 4, 1),
+    (Book::Malachi, 4, 1, 3, 3, 19),
+    (Book::Malachi, 4, 4, 4, 3, 24),
+    (Book::Malachi, 4, 5, 6, 3, 22),
+];
+
+// Book, KJV chapter, first and last verse, Synodal chapter and first verse
+#[rustfmt::skip]
+static SYNODAL_SHIFTS: &[(Book, u8, u8, u8, u8, u8)] = &[
+    (Book::Romans, 16, 25, 27, 14, 24),
+];
+
+// Psalms whose titles are counted as one verse in the Hebrew text
+#[rustfmt::skip]
+static PSALM_TITLES: &[u8] = &[
+    3, 4, 5, 6, 7, 8, 9, 12, 13, 18, 19, 20, 21, 22, 30, 31, 34, 36, 38, 39, 40, 41, 42, 44, 45,
+    46, 47, 48, 49, 53, 55, 56, 57, 58, 59, 61, 62, 63, 64, 65, 67, 68, 69, 70, 75, 76, 77, 80,
+    81, 83, 84, 85, 88, 89, 92, 102, 108, 140, 142,
+];
+
+// Psalms whose titles are counted as two verses in the Hebrew text
+static PSALM_LONG_TITLES: &[u8] = &[51, 52, 54, 60];
+
+impl Versification {
+    /// Number of chapters in the book
+    pub fn chapters(self, book: Book) -> u8 {
+        counts(self)[book.index()].len() as u8
+    }
+
+    /// Number of verses in the chapter, or `None` when there is no such chapter
+    pub fn verses(self, book: Book, chapter: u8) -> Option<u8> {
+        match chapter {
+            0 => None,
+            chapter => counts(self)[book.index()]
+                .get(chapter as usize - 1)
+                .cloned(),
+        }
+    }
+
+    /// Converts verse numbered in this scheme into the verses it corresponds
+    /// to in another scheme. Several verses are returned when the verse is
+    /// split there, and verses merged there give the same result
+    pub fn convert(self, book: Book, point: VersePoint, to: Versification) -> Vec<VersePoint> {
+        let mut result = Vec::new();
+        for kjv in to_kjv(self, book, point) {
+            for point in from_kjv(to, book, kjv) {
+                if !result.contains(&point) {
+                    result.push(point);
+                }
+            }
+        }
+        result
+    }
+}
+
+impl BibleReference {
+    /// Converts resolved reference from one versification into another.
+    ///
+    /// Verses are converted one by one, so a whole chapter may become part of
+    /// a chapter or several chapters (KJV Psalm 10 is Septuagint Psalm 9:22-39).
+    /// Converted reference has no spans. Returns `None` if the book is not resolved
+    pub fn convert(&self, from: Versification, to: Versification) -> Option<BibleReference> {
+        let book = self.book_id?;
+        let mut points = Vec::new();
+        for location in &self.locations {
+            for point in location_points(from, book, location) {
+                for point in from.convert(book, point, to) {
+                    if points.last() != Some(&point) {
+                        points.push(point);
+                    }
+                }
+            }
+        }
+
+        let locations = group_points(to, book, &points);
+        Some(BibleReference {
+            book: self.book.clone(),
+            book_id: self.book_id,
+            segments: locations
+                .iter()
+                .flat_map(|location| location.segments())
+                .collect(),
+            locations,
+            span: Default::default(),
+            book_span: Default::default(),
+            location_spans: vec![],
+            inferred: self.inferred,
+            typography: self.typography,
+            book_end: self.book_end.clone(),
+        })
+    }
+}
+
+/// Every verse of the location, in order
+fn location_points(scheme: Versification, book: Book, location: &VerseLocation) -> Vec<VersePoint> {
+    let mut points = Vec::new();
+    if let Some(range) = location.range {
+        for chapter in range.start.chapter..=range.end.chapter {
+            let first = if chapter == range.start.chapter {
+                range.start.verse
+            } else {
+                1
+            };
+            let last = if chapter == range.end.chapter {
+                range.end.verse
+            } else {
+                scheme.verses(book, chapter).unwrap_or(0)
+            };
+            points.extend((first..=last).map(|verse| VersePoint { chapter, verse }));
+        }
+        return points;
+    }
+
+    for &chapter in &location.chapters {
+        match location.verses {
+            Some(ref verses) => {
+                points.extend(verses.iter().map(|&verse| VersePoint { chapter, verse }))
+            }
+            None => {
+                let count = scheme.verses(book, chapter).unwrap_or(0);
+                points.extend((1..=count).map(|verse| VersePoint { chapter, verse }))
+            }
+        }
+    }
+    points
+}
+
+/// Groups verses into locations, using whole chapters where every verse is present
+fn group_points(scheme: Versification, book: Book, points: &[VersePoint]) -> Vec<VerseLocation> {
+    let mut locations: Vec<VerseLocation> = Vec::new();
+    let mut index = 0;
+    while index < points.len() {
+        let chapter = points[index].chapter;
+        let count = points[index..]
+            .iter()
+            .take_while(|point| point.chapter == chapter)
+            .count();
+        let verses: Vec<u8> = points[index..index + count]
+            .iter()
+            .map(|point| point.verse)
+            .collect();
+        index += count;
+
+        let whole = scheme
+            .verses(book, chapter)
+            .is_some_and(|total| verses.iter().cloned().eq(1..=total));
+        if !whole {
+            locations.push(VerseLocation {
+                chapters: vec![chapter],
+                verses: Some(verses),
+                range: None,
+                parts: vec![],
+                open_ranges: vec![],
+            });
+            continue;
+        }
+        match locations.last_mut() {
+            Some(ref mut location) if location.verses.is_none() => location.chapters.push(chapter),
+            _ => locations.push(VerseLocation {
+                chapters: vec![chapter],
+                verses: None,
+                range: None,
+                parts: vec![],
+                open_ranges: vec![],
+            }),
+        }
+    }
+    locations
+}
+
+/// KJV verses corresponding to the verse of the scheme
+fn to_kjv(scheme: Versification, book: Book, point: VersePoint) -> Vec<VersePoint> {
+    if scheme == Versification::Kjv {
+        return vec![point];
+    }
+    let points: Vec<VersePoint> = mappings(scheme)
+        .iter()
+        .filter(|mapping| mapping.book == book)
+        .flat_map(|mapping| translate(mapping.other, mapping.kjv, point))
+        .collect();
+    if points.is_empty() {
+        vec![point]
+    } else {
+        points
+    }
+}
+
+/// Verses of the scheme corresponding to the KJV verse
+fn from_kjv(scheme: Versification, book: Book, point: VersePoint) -> Vec<VersePoint> {
+    if scheme == Versification::Kjv {
+        return vec![point];
+    }
+    let points: Vec<VersePoint> = mappings(scheme)
+        .iter()
+        .filter(|mapping| mapping.book == book)
+        .flat_map(|mapping| translate(mapping.kjv, mapping.other, point))
+        .collect();
+    if points.is_empty() {
+        vec![point]
+    } else {
+        points
+    }
+}
+
+/// Moves the verse from one run into another: one to one when they have
+/// the same length, otherwise into the whole run (split) or its first verse (merge)
+fn translate(from: Verses, to: Verses, point: VersePoint) -> Vec<VersePoint> {
+    if point.chapter != from.chapter || point.verse < from.first || point.verse > from.last {
+        return vec![];
+    }
+    let verse = |verse| VersePoint {
+        chapter: to.chapter,
+        verse,
+    };
+    if from.last - from.first == to.last - to.first {
+        vec![verse(to.first + (point.verse - from.first))]
+    } else if from.first == from.last {
+        (to.first..=to.last).map(verse).collect()
+    } else {
+        vec![verse(to.first)]
+    }
+}
+
+/// Bundled runs of the scheme
+fn mappings(scheme: Versification) -> &'static [Mapping] {
+    lazy_static! {
+        static ref MAPPINGS: HashMap<Versification, Vec<Mapping>> = {
+            let mut map = HashMap::new();
+            let psalms = psalms();
+
+            let mut hebrew = shifts(HEBREW_SHIFTS);
+            hebrew.extend(HEBREW_SPLITS.iter().map(
+                |&(book, chapter, verse, other, other_verse)| Mapping {
+                    book,
+                    kjv: Verses {
+                        chapter,
+                        first: verse,
+                        last: verse,
+                    },
+                    other: Verses {
+                        chapter: other,
+                        first: other_verse,
+                        last: other_verse,
+                    },
+                },
+            ));
+            hebrew.extend(psalms.iter().map(|&(kjv, hebrew, _)| Mapping {
+                book: Book::Psalms,
+                kjv,
+                other: hebrew,
+            }));
+            map.insert(Versification::Hebrew, hebrew);
+
+            let greek: Vec<Mapping> = psalms
+                .iter()
+                .map(|&(kjv, _, greek)| Mapping {
+                    book: Book::Psalms,
+                    kjv,
+                    other: greek,
+                }).collect();
+
+            let mut septuagint = shifts(SEPTUAGINT_SHIFTS);
+            septuagint.extend(greek.iter().cloned());
+            map.insert(Versification::Septuagint, septuagint);
+
+            map.insert(Versification::Vulgate, greek.clone());
+
+            let mut synodal = shifts(SYNODAL_SHIFTS);
+            synodal.extend(greek.iter().cloned());
+            map.insert(Versification::Synodal, synodal);
+
+            map
+        };
+    }
+
+    MAPPINGS
+        .get(&scheme)
+        .map_or(&[], |mappings| mappings.as_slice())
+}
+
+fn shifts(table: &[(Book, u8, u8, u8, u8, u8)]) -> Vec<Mapping> {
+    table
+        .iter()
+        .map(
+            |&(book, chapter, first, last, other, other_first)| Mapping {
+                book,
+                kjv: Verses {
+                    chapter,
+                    first,
+                    last,
+                },
+                other: Verses {
+                    chapter: other,
+                    first: other_first,
+                    last: other_first + (last - first),
+                },
+            },
+        )
+        .collect()
+}
+
+/// Runs of every KJV Psalm with their Hebrew and Greek numbering.
+/// Titles make the first KJV verse correspond to several Hebrew verses,
+/// and the Greek text joins and splits some Psalms of the Hebrew text
+fn psalms() -> Vec<(Verses, Verses, Verses)> {
+    let mut runs = Vec::new();
+    for (index, &count) in VERSE_COUNTS[Book::Psalms.index()].iter().enumerate() {
+        let chapter = index as u8 + 1;
+        let title = if PSALM_LONG_TITLES.contains(&chapter) {
+            2
+        } else if PSALM_TITLES.contains(&chapter) {
+            1
+        } else {
+            0
+        };
+
+        let mut chapter_runs = vec![];
+        if title > 0 {
+            chapter_runs.push((
+                Verses {
+                    chapter,
+                    first: 1,
+                    last: 1,
+                },
+                Verses {
+                    chapter,
+                    first: 1,
+                    last: 1 + title,
+                },
+            ));
+            chapter_runs.push((
+                Verses {
+                    chapter,
+                    first: 2,
+                    last: count,
+                },
+                Verses {
+                    chapter,
+                    first: 2 + title,
+                    last: count + title,
+                },
+            ));
+        } else {
+            // Greek text splits these Psalms in two
+            let breaks: &[u8] = match chapter {
+                116 => &[9],
+                147 => &[11],
+                _ => &[],
+            };
+            let mut first = 1;
+            for &last in breaks.iter().chain(Some(&count)) {
+                let verses = Verses {
+                    chapter,
+                    first,
+                    last,
+                };
+                chapter_runs.push((verses, verses));
+                first = last + 1;
+            }
+        }
+
+        for (kjv, hebrew) in chapter_runs {
+            runs.push((kjv, hebrew, greek_psalm(hebrew)));
+        }
+    }
+    runs
+}
+
+/// Greek numbering of the Hebrew Psalm verses, which never cross
+/// a Greek chapter boundary
+fn greek_psalm(hebrew: Verses) -> Verses {
+    let (chapter, offset) = match (hebrew.chapter, hebrew.first) {
+        (1..=9, _) | (148..=150, _) => (hebrew.chapter, 0),
+        (10, _) => (9, 21),
+        (11..=113, _) | (117..=146, _) => (hebrew.chapter - 1, 0),
+        (114, _) => (113, 0),
+        (115, _) => (113, 8),
+        (116, 1..=9) => (114, 0),
+        (116, _) => (115, -9),
+        (147, 1..=11) => (146, 0),
+        (147, _) => (147, -11),
+        _ => (hebrew.chapter, 0),
+    };
+    Verses {
+        chapter,
+        first: (i16::from(hebrew.first) + offset) as u8,
+        last: (i16::from(hebrew.last) + offset) as u8,
+    }
+}
+
+/// Verse counts of the scheme, derived from the KJV counts and the runs
+fn counts(scheme: Versification) -> &'static [Vec<u8>] {
+    lazy_static! {
+        static ref COUNTS: HashMap<Versification, Vec<Vec<u8>>> = [
+            Versification::Kjv,
+            Versification::Hebrew,
+            Versification::Septuagint,
+            Versification::Vulgate,
+            Versification::Synodal,
+        ]
+        .iter()
+        .map(|&scheme| {
+            let counts = BOOKS
+                .iter()
+                .map(|&book| {
+                    let mut chapters: Vec<u8> = vec![];
+                    for (index, &count) in VERSE_COUNTS[book.index()].iter().enumerate() {
+                        for verse in 1..=count {
+                            let point = VersePoint {
+                                chapter: index as u8 + 1,
+                                verse,
+                            };
+                            for point in from_kjv(scheme, book, point) {
+                                let chapter = point.chapter as usize;
+                                if chapters.len() < chapter {
+                                    chapters.resize(chapter, 0);
+                                }
+                                chapters[chapter - 1] = chapters[chapter - 1].max(point.verse);
+                            }
+                        }
+                    }
+                    chapters
+                }).collect();
+            (scheme, counts)
+        }).collect();
+    }
+
+    COUNTS.get(&scheme).map_or(&[], |counts| counts.as_slice())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    #[cfg(all(feature = "english", feature = "russian"))]
+    use parse;
+
+    fn point(chapter: u8, verse: u8) -> VersePoint {
+        VersePoint { chapter, verse }
+    }
+
+    #[test]
+    fn test_shift_tables_fit_kjv() {
+        for table in [HEBREW_SHIFTS, SEPTUAGINT_SHIFTS, SYNODAL_SHIFTS].iter() {
+            for &(book, chapter, first, last, _, _) in table.iter() {
+                assert!(first <= last);
+                assert!(Versification::Kjv.verses(book, chapter).unwrap() >= last);
+            }
+        }
+    }
+
+    #[test]
+    fn test_counts() {
+        let kjv = Versification::Kjv;
+        assert_eq!(kjv.chapters(Book::Psalms), 150);
+        assert_eq!(kjv.verses(Book::Psalms, 119), Some(176));
+
+        let hebrew = Versification::Hebrew;
+        assert_eq!(hebrew.chapters(Book::Malachi), 3);
+        assert_eq!(hebrew.verses(Book::Malachi, 3), Some(24));
+        assert_eq!(hebrew.chapters(Book::Joel), 4);
+        assert_eq!(hebrew.verses(Book::Psalms, 51), Some(21));
+        assert_eq!(hebrew.verses(Book::Exodus, 7), Some(29));
+
+        let synodal = Versification::Synodal;
+        assert_eq!(synodal.chapters(Book::Psalms), 150);
+        assert_eq!(synodal.verses(Book::Psalms, 9), Some(39));
+        assert_eq!(synodal.verses(Book::Psalms, 113), Some(26));
+        assert_eq!(synodal.verses(Book::Psalms, 114), Some(9));
+        assert_eq!(synodal.verses(Book::Psalms, 115), Some(10));
+        assert_eq!(synodal.chapters(Book::Malachi), 4);
+    }
+
+    #[test]
+    fn test_convert_verse() {
+        let kjv = Versification::Kjv;
+        let synodal = Versification::Synodal;
+        let hebrew = Versification::Hebrew;
+
+        assert_eq!(
+            synodal.convert(Book::Psalms, point(22, 1), kjv),
+            [point(23, 1)]
+        );
+        assert_eq!(
+            kjv.convert(Book::Psalms, point(51, 1), synodal),
+            [point(50, 1), point(50, 2), point(50, 3)]
+        );
+        assert_eq!(
+            synodal.convert(Book::Psalms, point(50, 2), kjv),
+            [point(51, 1)]
+        );
+        assert_eq!(
+            kjv.convert(Book::Psalms, point(10, 1), synodal),
+            [point(9, 22)]
+        );
+        assert_eq!(
+            kjv.convert(Book::Malachi, point(4, 5), hebrew),
+            [point(3, 23)]
+        );
+        assert_eq!(
+            hebrew.convert(Book::Psalms, point(51, 3), synodal),
+            [point(50, 1), point(50, 2), point(50, 3)]
+        );
+        assert_eq!(
+            kjv.convert(Book::Romans, point(16, 25), synodal),
+            [point(14, 24)]
+        );
+        assert_eq!(
+            kjv.convert(Book::Genesis, point(1, 1), hebrew),
+            [point(1, 1)]
+        );
+    }
+
+    #[cfg(all(feature = "english", feature = "russian"))]
+    #[test]
+    fn test_convert_reference() {
+        let r = parse("Пс 22").remove(0);
+        let kjv = r
+            .convert(Versification::Synodal, Versification::Kjv)
+            .unwrap();
+        assert_eq!(kjv.locations.len(), 1);
+        assert_eq!(kjv.locations[0].chapters, [23]);
+        assert_eq!(kjv.locations[0].verses, None);
+
+        let r = parse("Mal 4").remove(0);
+        let hebrew = r
+            .convert(Versification::Kjv, Versification::Hebrew)
+            .unwrap();
+        assert_eq!(hebrew.locations[0].chapters, [3]);
+        assert_eq!(
+            hebrew.locations[0].verses,
+            Some((19..=24).collect::<Vec<u8>>())
+        );
+
+        let r = parse("Ps 10").remove(0);
+        let greek = r
+            .convert(Versification::Kjv, Versification::Septuagint)
+            .unwrap();
+        assert_eq!(greek.locations[0].chapters, [9]);
+        assert_eq!(
+            greek.locations[0].verses,
+            Some((22..=39).collect::<Vec<u8>>())
+        );
+
+        let r = parse("Пс 9").remove(0);
+        let kjv = r
+            .convert(Versification::Synodal, Versification::Kjv)
+            .unwrap();
+        assert_eq!(kjv.locations.len(), 1);
+        assert_eq!(kjv.locations[0].chapters, [9, 10]);
+    }
+}